@@ -1,9 +1,19 @@
+pub use crate::features::budget::{BudgetPattern, BudgetPatternKind};
 pub use crate::features::chrome_common::{
     AuthChallengeResponse, AuthChallengeResponseResponse, CaptureScreenshotFormat,
     CaptureScreenshotParams, ClipViewport, ScreenShotConfig, ScreenshotParams, Viewport, WaitFor,
     WaitForDelay, WaitForIdleNetwork, WaitForSelector,
 };
+pub use crate::features::fingerprint::NearDuplicate;
+pub use crate::features::frontier::{FrontierScore, FrontierScorer, FrontierStrategy};
+pub use crate::features::incremental::IncrementalCrawl;
+pub use crate::features::normalize::UrlNormalization;
 pub use crate::features::openai_common::GPTConfigs;
+pub use crate::features::politeness::{AdaptiveThrottle, HostPoliteness};
+pub use crate::features::response_filter::{ResponseFilter, ResponseRejection};
+pub use crate::features::retry::{RetryErrorKind, RetryPolicy};
+pub use crate::features::storage::DiskStorage;
+pub use crate::features::trap::{TrapDetection, TrapRule};
 use crate::website::CronType;
 use compact_str::CompactString;
 use hashbrown::HashMap;
@@ -39,6 +49,10 @@ type AllowList = Box<regex::RegexSet>;
 pub struct Configuration {
     /// Respect robots.txt file and not scrape not allowed files. This may slow down crawls if robots.txt file has a delay included.
     pub respect_robots_txt: bool,
+    /// How long the robots.txt rules of a host are kept before fetching the file again. Defaults to 24 hours.
+    pub robots_ttl: Option<Duration>,
+    /// Respect the meta robots tags, X-Robots-Tag headers and rel=nofollow anchors of the pages.
+    pub respect_robots_meta: bool,
     /// Allow sub-domains.
     pub subdomains: bool,
     /// Allow all tlds for domain.
@@ -78,10 +92,12 @@ pub struct Configuration {
     #[cfg(feature = "cron")]
     /// The type of cron to run either crawl or scrape.
     pub cron_type: CronType,
-    /// The max depth to crawl for a website.
+    /// The max depth to crawl for a website by the link hops from the start page.
     pub depth: usize,
     /// The depth to crawl pertaining to the root.
     pub depth_distance: usize,
+    /// Measure the depth by the url path segments instead of the link hops from the start page.
+    pub path_depth: bool,
     /// Cache the page following HTTP caching rules.
     #[cfg(any(feature = "cache", feature = "chrome"))]
     pub cache: bool,
@@ -108,13 +124,24 @@ pub struct Configuration {
     pub evaluate_on_new_document: Option<Box<String>>,
     /// Crawl budget for the paths. This helps prevent crawling extra pages and limiting the amount.
     pub budget: Option<hashbrown::HashMap<case_insensitive_string::CaseInsensitiveString, u32>>,
+    /// Stop the crawl after the time. The requests in flight finish before the crawl ends.
+    pub max_crawl_duration: Option<Box<Duration>>,
+    /// Stop the crawl after the bytes of page content are downloaded. The requests in flight finish before the crawl ends.
+    pub max_total_bytes: Option<u64>,
     /// If wild card budgeting is found for the website.
     pub wild_card_budgeting: bool,
+    /// Crawl budgets for the urls matching glob or regex patterns.
+    pub budget_patterns: Option<Vec<BudgetPattern>>,
+    /// Crawl budget for each host. This helps limit the pages crawled on external domains and subdomains.
+    pub host_budget:
+        Option<hashbrown::HashMap<case_insensitive_string::CaseInsensitiveString, u32>>,
     /// External domains to include case-insensitive.
     pub external_domains_caseless:
         Box<hashbrown::HashSet<case_insensitive_string::CaseInsensitiveString>>,
     /// Collect all the resources found on the page.
     pub full_resources: bool,
+    /// Record the links between the pages crawled.
+    pub link_graph: bool,
     #[cfg(feature = "chrome")]
     /// Wait for options for the page.
     pub wait_for: Option<WaitFor>,
@@ -138,6 +165,32 @@ pub struct Configuration {
     pub execution_scripts: Option<HashMap<String, String>>,
     /// Use a shared queue strategy when crawling. This can scale workloads evenly that do not need priority.
     pub shared_queue: bool,
+    /// The file to periodically store the crawl state in. The crawl can be continued from the file with `Website::resume_from`.
+    pub checkpoint_path: Option<Box<std::path::PathBuf>>,
+    /// The minimum time between checkpoint writes. If not set the checkpoint is written after every crawl round.
+    pub checkpoint_interval: Option<Box<Duration>>,
+    /// The order the links found are crawled in.
+    pub frontier: FrontierStrategy,
+    /// Score the links found for the frontier. Links with a higher score are crawled first with the `BestFirst` frontier.
+    pub frontier_score: Option<FrontierScorer>,
+    /// Schedule the requests per host with a concurrency limit and delay for each host. The crawl delay is applied per host instead of globally.
+    pub host_politeness: Option<HostPoliteness>,
+    /// Slow a host down on rate limit responses or rising latency and speed back up once the responses are healthy.
+    pub adaptive_throttle: Option<AdaptiveThrottle>,
+    /// Retry the requests that failed with a transient error.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Normalize the urls found and the start url before they are deduplicated.
+    pub url_normalization: Option<Box<UrlNormalization>>,
+    /// Detect pages with nearly the same content as an earlier page and stop following their links.
+    pub near_duplicate: Option<NearDuplicate>,
+    /// Reject links that look like crawler traps.
+    pub trap_detection: Option<TrapDetection>,
+    /// Recrawl only the pages that changed since the last crawl.
+    pub incremental: Option<IncrementalCrawl>,
+    /// Drop the responses by MIME type, Content-Length and status code before the body is downloaded.
+    pub response_filter: Option<ResponseFilter>,
+    /// Keep the visited links and the frontier on disk once they are over the memory limit.
+    pub disk_storage: Option<DiskStorage>,
     /// The blacklist urls.
     blacklist: AllowList,
     /// The whitelist urls.
@@ -259,12 +312,24 @@ impl Configuration {
         }
     }
 
-    /// Respect robots.txt file.
+    /// Respect robots.txt file. The robots.txt of every host crawled is fetched on first contact and the rules of the host apply to its urls.
     pub fn with_respect_robots_txt(&mut self, respect_robots_txt: bool) -> &mut Self {
         self.respect_robots_txt = respect_robots_txt;
         self
     }
 
+    /// How long the robots.txt rules of a host are kept before fetching the file again. The rules are fetched again during long crawls and on the next crawl once expired. Defaults to 24 hours.
+    pub fn with_robots_ttl(&mut self, robots_ttl: Option<Duration>) -> &mut Self {
+        self.robots_ttl = robots_ttl;
+        self
+    }
+
+    /// Respect the meta robots tags, X-Robots-Tag headers and rel=nofollow anchors of the pages. The links of nofollow pages and rel=nofollow anchors are not followed and noindex pages are flagged with `Page::noindex`.
+    pub fn with_respect_robots_meta(&mut self, respect_robots_meta: bool) -> &mut Self {
+        self.respect_robots_meta = respect_robots_meta;
+        self
+    }
+
     /// Include subdomains detection.
     pub fn with_subdomains(&mut self, subdomains: bool) -> &mut Self {
         self.subdomains = subdomains;
@@ -396,6 +461,97 @@ impl Configuration {
         self
     }
 
+    /// Periodically store the crawl state to the file path to allow resuming the crawl with `Website::resume_from`. Set the interval to throttle how often the file is written.
+    pub fn with_checkpoint(
+        &mut self,
+        checkpoint_path: Option<&str>,
+        checkpoint_interval: Option<Duration>,
+    ) -> &mut Self {
+        match checkpoint_path {
+            Some(checkpoint_path) => {
+                self.checkpoint_path = Some(Box::new(checkpoint_path.into()));
+                self.checkpoint_interval = checkpoint_interval.map(Box::new);
+            }
+            _ => {
+                self.checkpoint_path = None;
+                self.checkpoint_interval = None;
+            }
+        };
+        self
+    }
+
+    /// Set the order the links found are crawled in. The default is breadth first.
+    pub fn with_frontier(&mut self, frontier: FrontierStrategy) -> &mut Self {
+        self.frontier = frontier;
+        self
+    }
+
+    /// Score the links found with the url, the anchor text and the page the link was found on. Use with the `BestFirst` frontier to crawl the links with the highest score first.
+    pub fn with_frontier_score(&mut self, frontier_score: Option<FrontierScore>) -> &mut Self {
+        self.frontier_score = frontier_score.map(FrontierScorer);
+        self
+    }
+
+    /// Schedule the requests per host taking turns between the hosts. Each host has its own concurrency limit and delay between requests including the robots `Crawl-delay`.
+    pub fn with_host_politeness(&mut self, host_politeness: Option<HostPoliteness>) -> &mut Self {
+        self.host_politeness = host_politeness;
+        self
+    }
+
+    /// Slow a host down on 429 and 503 responses or rising latency following the Retry-After header. The rate limited urls are crawled again and the crawl status is blocked while a host is backing off.
+    pub fn with_adaptive_throttle(
+        &mut self,
+        adaptive_throttle: Option<AdaptiveThrottle>,
+    ) -> &mut Self {
+        self.adaptive_throttle = adaptive_throttle;
+        self
+    }
+
+    /// Retry requests that failed with a retryable status code or network error with exponential backoff. The attempts are recorded on the page.
+    pub fn with_retry_policy(&mut self, retry_policy: Option<RetryPolicy>) -> &mut Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Normalize the urls found and the start url so variants of the same page like reordered query params or tracking params are only crawled once.
+    pub fn with_url_normalization(
+        &mut self,
+        url_normalization: Option<UrlNormalization>,
+    ) -> &mut Self {
+        self.url_normalization = url_normalization.map(Box::new);
+        self
+    }
+
+    /// Fingerprint the text of every page and mark pages with nearly the same content as an earlier page as duplicates. The links of the duplicates are not followed.
+    pub fn with_near_duplicate(&mut self, near_duplicate: Option<NearDuplicate>) -> &mut Self {
+        self.near_duplicate = near_duplicate;
+        self
+    }
+
+    /// Reject links that look like crawler traps with repeated path segments, long urls, too many query params, session ids or too many urls with the same pattern.
+    pub fn with_trap_detection(&mut self, trap_detection: Option<TrapDetection>) -> &mut Self {
+        self.trap_detection = trap_detection;
+        self
+    }
+
+    /// Recrawl only the pages that changed since the last crawl. The ETag and Last-Modified validators of every page are stored and sent back with `If-None-Match` and `If-Modified-Since`. Pages answered with `304 Not Modified` or with the same content are marked as unchanged and their links are not followed unless `follow_unchanged` is set.
+    pub fn with_incremental(&mut self, incremental: Option<IncrementalCrawl>) -> &mut Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Drop the responses by MIME type, Content-Length and status code. The headers are checked before the body is downloaded and the download stops once the body is over the max content length. Chrome pages are checked before the page is rendered. The decentralized workers do not apply the filter.
+    pub fn with_response_filter(&mut self, response_filter: Option<ResponseFilter>) -> &mut Self {
+        self.response_filter = response_filter;
+        self
+    }
+
+    /// Keep the visited links and the frontier on disk once they are over the memory limit of the storage. The links on disk are deduplicated exactly with a Bloom filter in front to skip most disk reads. Only the breadth first frontier is kept on disk, the other strategies keep the frontier in memory.
+    pub fn with_disk_storage(&mut self, disk_storage: Option<DiskStorage>) -> &mut Self {
+        self.disk_storage = disk_storage;
+        self
+    }
+
     /// Add blacklist urls to ignore.
     pub fn with_blacklist_url<T>(&mut self, blacklist_url: Option<Vec<T>>) -> &mut Self
     where
@@ -447,6 +603,12 @@ impl Configuration {
         self
     }
 
+    /// Record the links between the pages with the anchor text, rel and nofollow flag. The edges have no anchor text or rel with the `decentralized` flag.
+    pub fn with_link_graph(&mut self, link_graph: bool) -> &mut Self {
+        self.link_graph = link_graph;
+        self
+    }
+
     #[cfg(feature = "cron")]
     /// Setup cron jobs to run. This does nothing without the `cron` flag enabled.
     pub fn with_cron(&mut self, cron_str: &str, cron_type: CronType) -> &mut Self {
@@ -467,6 +629,18 @@ impl Configuration {
         self
     }
 
+    /// Stop the crawl after the time. Set to None to disable.
+    pub fn with_max_crawl_duration(&mut self, max_crawl_duration: Option<Duration>) -> &mut Self {
+        self.max_crawl_duration = max_crawl_duration.map(Box::new);
+        self
+    }
+
+    /// Stop the crawl after the bytes of page content are downloaded. Set to None to disable.
+    pub fn with_max_total_bytes(&mut self, max_total_bytes: Option<u64>) -> &mut Self {
+        self.max_total_bytes = max_total_bytes;
+        self
+    }
+
     #[cfg(feature = "chrome")]
     /// Set the authentiation challenge response. This does nothing without the feat flag `chrome` enabled.
     pub fn with_auth_challenge_response(
@@ -505,9 +679,17 @@ impl Configuration {
         self
     }
 
-    /// Set a crawl depth limit. If the value is 0 there is no limit. This does nothing without the feat flag `budget` enabled.
+    /// Set a crawl depth limit by the link hops from the start page. The start page is the first level so a depth of 1 only crawls the start page. If the value is 0 there is no limit.
     pub fn with_depth(&mut self, depth: usize) -> &mut Self {
         self.depth = depth;
+        self.depth_distance = 0;
+        self
+    }
+
+    /// Measure the crawl depth by the url path segments instead of the link hops from the start page.
+    pub fn with_path_depth(&mut self, path_depth: bool) -> &mut Self {
+        self.path_depth = path_depth;
+        self.depth_distance = 0;
         self
     }
 
@@ -705,6 +887,26 @@ impl Configuration {
         self
     }
 
+    /// Set crawl budgets for the urls matching glob or regex patterns like `/products/*/reviews`. The pattern budgets apply alongside the path budgets.
+    pub fn with_budget_patterns(
+        &mut self,
+        budget_patterns: Option<Vec<BudgetPattern>>,
+    ) -> &mut Self {
+        self.budget_patterns = budget_patterns;
+        self
+    }
+
+    /// Set a crawl budget for each host like `docs.example.com`.
+    pub fn with_host_budget(&mut self, host_budget: Option<HashMap<&str, u32>>) -> &mut Self {
+        self.host_budget = host_budget.map(|budget| {
+            budget
+                .into_iter()
+                .map(|(host, limit)| (host.into(), limit))
+                .collect()
+        });
+        self
+    }
+
     /// Group external domains to treat the crawl as one. If None is passed this will clear all prior domains.
     pub fn with_external_domains<'a, 'b>(
         &mut self,
@@ -0,0 +1,408 @@
+use crate::CaseInsensitiveString;
+use hashbrown::HashMap;
+use url::Url;
+
+/// How the pattern of a budget matches the urls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum::EnumString, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BudgetPatternKind {
+    /// A glob where `*` matches any text in a path segment and `**` matches any text. The other characters including `?` match literally ignoring case.
+    #[default]
+    Glob,
+    /// A regex searched in the url. The capture groups split the budget so each captured value gets the limit. This does nothing without the `regex` flag enabled.
+    Regex,
+}
+
+/// A crawl budget for the urls matching a pattern. The pattern is matched on the path and query of the url like `/products/1/reviews?page=2`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BudgetPattern {
+    /// The pattern to match.
+    pub pattern: String,
+    /// How the pattern matches the urls.
+    pub kind: BudgetPatternKind,
+    /// The max amount of urls matching the pattern to crawl.
+    pub limit: u32,
+}
+
+impl BudgetPattern {
+    /// A budget for the urls matching the glob like `/products/*/reviews`.
+    pub fn glob(pattern: &str, limit: u32) -> Self {
+        Self {
+            pattern: pattern.into(),
+            kind: BudgetPatternKind::Glob,
+            limit,
+        }
+    }
+
+    /// A budget for the urls matching the regex like `^(/[^?]*)\?` for each query-string variant of a path.
+    pub fn regex(pattern: &str, limit: u32) -> Self {
+        Self {
+            pattern: pattern.into(),
+            kind: BudgetPatternKind::Regex,
+            limit,
+        }
+    }
+}
+
+/// The budget used for a key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BudgetUsage {
+    /// The limit of the budget.
+    pub limit: u32,
+    /// The amount of urls counted against the budget.
+    pub used: u32,
+    /// The amount of urls counted for each captured value of a regex with capture groups.
+    pub groups: HashMap<String, u32>,
+}
+
+/// The budgets used during the crawl.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrawlBudgetUsage {
+    /// The path prefix budgets by path.
+    pub paths: HashMap<String, BudgetUsage>,
+    /// The pattern budgets by pattern.
+    pub patterns: HashMap<String, BudgetUsage>,
+    /// The host budgets by host.
+    pub hosts: HashMap<String, BudgetUsage>,
+}
+
+/// Match the glob ignoring ascii case. `*` does not cross a `/` and `**` matches any text.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        Some((b'*', rest)) => match rest.split_first() {
+            Some((b'*', rest)) => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+            _ => {
+                let end = text.iter().position(|c| *c == b'/').unwrap_or(text.len());
+                (0..=end).any(|i| glob_match(rest, &text[i..]))
+            }
+        },
+        Some((p, rest)) => match text.split_first() {
+            Some((t, text)) => p.eq_ignore_ascii_case(t) && glob_match(rest, text),
+            _ => false,
+        },
+        _ => text.is_empty(),
+    }
+}
+
+/// A pattern budget with the urls counted.
+#[derive(Debug, Clone)]
+struct PatternBudget {
+    /// The budget.
+    budget: BudgetPattern,
+    /// The compiled regex.
+    #[cfg(feature = "regex")]
+    regex: Option<regex::Regex>,
+    /// The amount of urls counted for each captured value. Patterns without capture groups count under an empty key.
+    used: HashMap<String, u32>,
+}
+
+impl PatternBudget {
+    /// A new pattern budget compiling the regex.
+    fn new(budget: BudgetPattern) -> Self {
+        Self {
+            #[cfg(feature = "regex")]
+            regex: match budget.kind {
+                BudgetPatternKind::Regex => match regex::Regex::new(&budget.pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        log::warn!("invalid budget regex {}: {}", budget.pattern, e);
+                        None
+                    }
+                },
+                _ => None,
+            },
+            budget,
+            used: HashMap::new(),
+        }
+    }
+
+    /// The key the url is counted under if the url matches.
+    #[cfg(feature = "regex")]
+    fn group(&self, target: &str) -> Option<String> {
+        match self.budget.kind {
+            BudgetPatternKind::Glob => {
+                if glob_match(self.budget.pattern.as_bytes(), target.as_bytes()) {
+                    Some(String::new())
+                } else {
+                    None
+                }
+            }
+            BudgetPatternKind::Regex => match &self.regex {
+                Some(regex) => regex.captures(target).map(|captures| {
+                    captures
+                        .iter()
+                        .skip(1)
+                        .flatten()
+                        .map(|c| c.as_str())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                }),
+                _ => None,
+            },
+        }
+    }
+
+    /// The key the url is counted under if the url matches.
+    #[cfg(not(feature = "regex"))]
+    fn group(&self, target: &str) -> Option<String> {
+        match self.budget.kind {
+            BudgetPatternKind::Glob
+                if glob_match(self.budget.pattern.as_bytes(), target.as_bytes()) =>
+            {
+                Some(String::new())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The path and query of the url the patterns match on.
+fn budget_target(url: &Url) -> String {
+    match url.query() {
+        Some(query) => string_concat!(url.path(), "?", query),
+        _ => url.path().to_string(),
+    }
+}
+
+/// Count the urls against the pattern and host budgets and keep the budgets used.
+#[derive(Debug, Clone, Default)]
+pub struct BudgetTracker {
+    /// The path prefix budgets at the start of the crawl.
+    paths: HashMap<CaseInsensitiveString, u32>,
+    /// The pattern budgets.
+    patterns: Vec<PatternBudget>,
+    /// The host budgets with the limit and the urls counted.
+    hosts: HashMap<CaseInsensitiveString, (u32, u32)>,
+}
+
+impl BudgetTracker {
+    /// A new tracker keeping the path prefix budgets to report the budget used.
+    pub fn new(
+        paths: &Option<HashMap<CaseInsensitiveString, u32>>,
+        patterns: &Option<Vec<BudgetPattern>>,
+        hosts: &Option<HashMap<CaseInsensitiveString, u32>>,
+    ) -> Self {
+        Self {
+            paths: paths.clone().unwrap_or_default(),
+            patterns: match patterns {
+                Some(patterns) => patterns.iter().cloned().map(PatternBudget::new).collect(),
+                _ => Default::default(),
+            },
+            hosts: match hosts {
+                Some(hosts) => hosts
+                    .iter()
+                    .map(|(host, limit)| (host.clone(), (*limit, 0)))
+                    .collect(),
+                _ => Default::default(),
+            },
+        }
+    }
+
+    /// The url is over a pattern or host budget.
+    pub fn is_over(&self, url: &Url) -> bool {
+        let over_host = match self.hosts.get(&CaseInsensitiveString::from(
+            url.host_str().unwrap_or_default(),
+        )) {
+            Some((limit, used)) => used >= limit,
+            _ => false,
+        };
+
+        over_host || {
+            let target = budget_target(url);
+
+            self.patterns
+                .iter()
+                .any(|pattern| match pattern.group(&target) {
+                    Some(group) => {
+                        pattern.used.get(&group).copied().unwrap_or_default()
+                            >= pattern.budget.limit
+                    }
+                    _ => false,
+                })
+        }
+    }
+
+    /// Count the url against the pattern and host budgets it matches.
+    pub fn consume(&mut self, url: &Url) {
+        match self.hosts.get_mut(&CaseInsensitiveString::from(
+            url.host_str().unwrap_or_default(),
+        )) {
+            Some((_, used)) => *used += 1,
+            _ => (),
+        }
+
+        let target = budget_target(url);
+
+        for pattern in self.patterns.iter_mut() {
+            match pattern.group(&target) {
+                Some(group) => *pattern.used.entry(group).or_insert(0) += 1,
+                _ => (),
+            }
+        }
+    }
+
+    /// The path prefix budgets at the start of the crawl.
+    pub fn path_limits(&self) -> &HashMap<CaseInsensitiveString, u32> {
+        &self.paths
+    }
+
+    /// Count the pattern and host budgets used before, like the usage kept in a checkpoint.
+    pub fn restore(&mut self, usage: &CrawlBudgetUsage) {
+        for (host, (_, used)) in self.hosts.iter_mut() {
+            match usage.hosts.get(host.inner().as_str()) {
+                Some(host_usage) => *used = host_usage.used,
+                _ => (),
+            }
+        }
+
+        for pattern in self.patterns.iter_mut() {
+            match usage.patterns.get(&pattern.budget.pattern) {
+                Some(pattern_usage) => {
+                    pattern.used = pattern_usage.groups.clone();
+
+                    // the urls of patterns without capture groups are counted under an empty key.
+                    let grouped: u32 = pattern_usage.groups.values().sum();
+
+                    if pattern_usage.used > grouped {
+                        pattern
+                            .used
+                            .insert(String::new(), pattern_usage.used - grouped);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// The budgets used with the path prefix budgets left.
+    pub fn usage(
+        &self,
+        remaining: &Option<HashMap<CaseInsensitiveString, u32>>,
+    ) -> CrawlBudgetUsage {
+        CrawlBudgetUsage {
+            paths: self
+                .paths
+                .iter()
+                .map(|(path, limit)| {
+                    let left = match remaining {
+                        Some(remaining) => remaining.get(path).copied().unwrap_or(*limit),
+                        _ => *limit,
+                    };
+
+                    (
+                        path.inner().to_string(),
+                        BudgetUsage {
+                            limit: *limit,
+                            used: limit.saturating_sub(left),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            patterns: self
+                .patterns
+                .iter()
+                .map(|pattern| {
+                    (
+                        pattern.budget.pattern.clone(),
+                        BudgetUsage {
+                            limit: pattern.budget.limit,
+                            used: pattern.used.values().sum(),
+                            groups: pattern
+                                .used
+                                .iter()
+                                .filter(|(group, _)| !group.is_empty())
+                                .map(|(group, used)| (group.clone(), *used))
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+            hosts: self
+                .hosts
+                .iter()
+                .map(|(host, (limit, used))| {
+                    (
+                        host.inner().to_string(),
+                        BudgetUsage {
+                            limit: *limit,
+                            used: *used,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[test]
+fn budget_patterns() {
+    let mut tracker = BudgetTracker::new(
+        &None,
+        &Some(vec![
+            BudgetPattern::glob("/products/*/reviews", 2),
+            BudgetPattern::glob("/search?**", 1),
+        ]),
+        &Some(HashMap::from([("docs.choosealicense.com".into(), 1)])),
+    );
+    let mut crawl = |url: &str| {
+        let url = Url::parse(url).unwrap();
+        let over = tracker.is_over(&url);
+        if !over {
+            tracker.consume(&url);
+        }
+        over
+    };
+
+    assert!(!crawl("https://choosealicense.com/products/1/reviews"));
+    assert!(!crawl("https://choosealicense.com/Products/2/reviews"));
+    assert!(crawl("https://choosealicense.com/products/3/reviews"));
+    assert!(!crawl(
+        "https://choosealicense.com/products/3/reviews/extra"
+    ));
+    assert!(!crawl("https://choosealicense.com/products/a/b/reviews"));
+    assert!(!crawl("https://choosealicense.com/search?q=mit/apache"));
+    assert!(crawl("https://choosealicense.com/search?q=gpl"));
+    assert!(!crawl("https://choosealicense.com/search"));
+    assert!(!crawl("https://docs.choosealicense.com/"));
+    assert!(crawl("https://docs.choosealicense.com/about/"));
+
+    let usage = tracker.usage(&None);
+
+    assert_eq!(usage.patterns["/products/*/reviews"].used, 2);
+    assert_eq!(usage.patterns["/search?**"].used, 1);
+    assert_eq!(usage.hosts["docs.choosealicense.com"].used, 1);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn budget_regex_groups() {
+    let mut tracker = BudgetTracker::new(
+        &Some(HashMap::from([("licenses".into(), 5)])),
+        &Some(vec![BudgetPattern::regex(r"^(/[^?]*)\?", 1)]),
+        &None,
+    );
+
+    for url in [
+        "https://choosealicense.com/licenses/?page=1",
+        "https://choosealicense.com/licenses/?page=2",
+        "https://choosealicense.com/about/?ref=nav",
+    ] {
+        let url = Url::parse(url).unwrap();
+        if !tracker.is_over(&url) {
+            tracker.consume(&url);
+        }
+    }
+
+    let usage = tracker.usage(&Some(HashMap::from([("licenses".into(), 3)])));
+    let pattern = &usage.patterns[r"^(/[^?]*)\?"];
+
+    assert_eq!(pattern.used, 2);
+    assert_eq!(pattern.groups.get("/licenses/"), Some(&1));
+    assert_eq!(pattern.groups.get("/about/"), Some(&1));
+    assert_eq!(usage.paths["licenses"].used, 2);
+}
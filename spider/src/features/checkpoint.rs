@@ -0,0 +1,590 @@
+use crate::features::budget::{BudgetUsage, CrawlBudgetUsage};
+use crate::features::link_hook::LinkMetadata;
+use crate::website::CrawlStatus;
+use crate::CaseInsensitiveString;
+use hashbrown::{HashMap, HashSet};
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The header written to the top of every checkpoint file.
+const CHECKPOINT_HEADER: &str = "spider-checkpoint 1";
+/// The bytes of the records buffered before they are sent to the checkpoint file.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// The state of a crawl that can be stored to disk and restored to resume the crawl.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    /// The base url of the crawl.
+    pub url: CaseInsensitiveString,
+    /// The status of the crawl when the checkpoint was taken.
+    pub status: CrawlStatus,
+    /// All URLs visited.
+    pub visited: HashSet<CaseInsensitiveString>,
+    /// The links waiting to be crawled.
+    pub frontier: HashSet<CaseInsensitiveString>,
+    /// The link hops from the start page for the links waiting to be crawled.
+    pub depths: HashMap<CaseInsensitiveString, usize>,
+    /// The frontier scores of the links waiting to be crawled.
+    pub scores: HashMap<CaseInsensitiveString, f64>,
+    /// The metadata from the link hook of the links waiting to be crawled.
+    pub metadata: HashMap<CaseInsensitiveString, LinkMetadata>,
+    /// The sitemaps waiting to be crawled.
+    pub sitemaps: HashSet<CaseInsensitiveString>,
+    /// The sitemaps crawled.
+    pub sitemaps_visited: HashSet<CaseInsensitiveString>,
+    /// The crawl budget for the paths at the start of the crawl.
+    pub budget: Option<HashMap<CaseInsensitiveString, u32>>,
+    /// The path, pattern and host budgets used before the checkpoint was taken.
+    pub budget_used: Option<CrawlBudgetUsage>,
+}
+
+/// Add a `kind value` record.
+fn push_record(out: &mut String, kind: &str, value: &str) {
+    out.push_str(kind);
+    out.push(' ');
+    out.push_str(value);
+    out.push('\n');
+}
+
+/// Add a `kind number link` record.
+fn push_number(out: &mut String, kind: &str, number: impl ToString, link: &CaseInsensitiveString) {
+    push_record(
+        out,
+        kind,
+        &string_concat!(number.to_string(), " ", link.inner()),
+    );
+}
+
+/// Add the `metadata link key value` records of a link. The fields are split with tabs.
+fn push_metadata(out: &mut String, link: &CaseInsensitiveString, metadata: &LinkMetadata) {
+    for (key, value) in metadata.iter() {
+        push_record(
+            out,
+            "metadata",
+            &string_concat!(link.inner(), "\t", escape(key), "\t", escape(value)),
+        );
+    }
+}
+
+/// Escape the tabs and line breaks of a record field.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Restore a record field escaped with `escape`.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => unescaped.push('\t'),
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(c) => unescaped.push(c),
+                _ => (),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+
+    unescaped
+}
+
+/// The temporary file written before the checkpoint is moved into place.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tmp.into()
+}
+
+/// Split a record value like `3 /licenses` into the count and the rest.
+fn split_count(value: &str) -> Option<(u32, &str)> {
+    let (count, rest) = value.split_once(' ')?;
+    Some((count.parse().ok()?, rest))
+}
+
+/// Split a record value like `5 3 /licenses` into the limit, the amount used and the rest.
+fn split_usage(value: &str) -> Option<(u32, u32, &str)> {
+    let (limit, rest) = split_count(value)?;
+    let (used, rest) = split_count(rest)?;
+    Some((limit, used, rest))
+}
+
+/// Encode the budgets used as `kind limit used key` records.
+fn encode_usage(out: &mut String, kind: &str, usage: &HashMap<String, BudgetUsage>) {
+    for (key, usage) in usage.iter() {
+        out.push_str(&string_concat!(
+            kind,
+            " ",
+            usage.limit.to_string(),
+            " ",
+            usage.used.to_string(),
+            " ",
+            key,
+            "\n"
+        ));
+    }
+}
+
+/// Decode a `limit used key` record of a budget used.
+fn decode_usage(usage: &mut HashMap<String, BudgetUsage>, value: &str) {
+    match split_usage(value) {
+        Some((limit, used, key)) => {
+            let entry = usage.entry(key.to_string()).or_default();
+            entry.limit = limit;
+            entry.used = used;
+        }
+        _ => (),
+    }
+}
+
+impl Checkpoint {
+    /// Encode the checkpoint as a line based document. Each line holds one record starting with the record kind.
+    pub fn encode(&self) -> String {
+        let mut out = String::with_capacity(
+            (self.visited.len() + self.frontier.len() + 4) * 64 + CHECKPOINT_HEADER.len(),
+        );
+
+        out.push_str(CHECKPOINT_HEADER);
+        out.push('\n');
+        out.push_str(&string_concat!("url ", self.url.inner(), "\n"));
+        out.push_str(&string_concat!("status ", self.status.to_string(), "\n"));
+
+        match &self.budget {
+            Some(budget) => {
+                for (path, limit) in budget.iter() {
+                    out.push_str(&string_concat!(
+                        "budget ",
+                        limit.to_string(),
+                        " ",
+                        path.inner(),
+                        "\n"
+                    ));
+                }
+            }
+            _ => (),
+        }
+
+        match &self.budget_used {
+            Some(usage) => {
+                encode_usage(&mut out, "used_path", &usage.paths);
+                encode_usage(&mut out, "used_host", &usage.hosts);
+                encode_usage(&mut out, "used_pattern", &usage.patterns);
+
+                // the pattern and the captured value are split with a tab since both can hold spaces.
+                for (pattern, pattern_usage) in usage.patterns.iter() {
+                    for (group, used) in pattern_usage.groups.iter() {
+                        out.push_str(&string_concat!(
+                            "used_group ",
+                            used.to_string(),
+                            " ",
+                            pattern,
+                            "\t",
+                            group,
+                            "\n"
+                        ));
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        for link in self.visited.iter() {
+            push_record(&mut out, "visited", link.inner());
+        }
+
+        for link in self.frontier.iter() {
+            push_record(&mut out, "frontier", link.inner());
+        }
+
+        for (link, depth) in self.depths.iter() {
+            push_number(&mut out, "depth", depth, link);
+        }
+
+        for (link, score) in self.scores.iter() {
+            push_number(&mut out, "score", score, link);
+        }
+
+        for (link, metadata) in self.metadata.iter() {
+            push_metadata(&mut out, link, metadata);
+        }
+
+        for link in self.sitemaps.iter() {
+            push_record(&mut out, "sitemap", link.inner());
+        }
+
+        for link in self.sitemaps_visited.iter() {
+            push_record(&mut out, "sitemap_visited", link.inner());
+        }
+
+        out
+    }
+
+    /// Decode a checkpoint created with `encode`.
+    pub fn decode(data: &str) -> Result<Self, Error> {
+        let mut lines = data.lines();
+
+        if lines.next() != Some(CHECKPOINT_HEADER) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "invalid checkpoint header",
+            ));
+        }
+
+        let mut checkpoint = Checkpoint::default();
+
+        for line in lines {
+            let (kind, value) = match line.split_once(' ') {
+                Some(record) => record,
+                _ => continue,
+            };
+
+            match kind {
+                "url" => checkpoint.url = value.into(),
+                "status" => {
+                    checkpoint.status = CrawlStatus::from_str(value).unwrap_or_default();
+                }
+                "budget" => match split_count(value) {
+                    Some((limit, path)) => {
+                        checkpoint
+                            .budget
+                            .get_or_insert_with(HashMap::new)
+                            .insert(path.into(), limit);
+                    }
+                    _ => (),
+                },
+                "used_path" => decode_usage(
+                    &mut checkpoint
+                        .budget_used
+                        .get_or_insert_with(Default::default)
+                        .paths,
+                    value,
+                ),
+                "used_host" => decode_usage(
+                    &mut checkpoint
+                        .budget_used
+                        .get_or_insert_with(Default::default)
+                        .hosts,
+                    value,
+                ),
+                "used_pattern" => decode_usage(
+                    &mut checkpoint
+                        .budget_used
+                        .get_or_insert_with(Default::default)
+                        .patterns,
+                    value,
+                ),
+                "used_group" => match split_count(value) {
+                    Some((used, rest)) => match rest.split_once('\t') {
+                        Some((pattern, group)) => {
+                            checkpoint
+                                .budget_used
+                                .get_or_insert_with(Default::default)
+                                .patterns
+                                .entry(pattern.to_string())
+                                .or_default()
+                                .groups
+                                .insert(group.to_string(), used);
+                        }
+                        _ => (),
+                    },
+                    _ => (),
+                },
+                "visited" => {
+                    checkpoint.visited.insert(value.into());
+                }
+                "frontier" => {
+                    checkpoint.frontier.insert(value.into());
+                }
+                "depth" => match value.split_once(' ') {
+                    Some((depth, link)) => match depth.parse::<usize>() {
+                        Ok(depth) => {
+                            checkpoint.depths.insert(link.into(), depth);
+                        }
+                        _ => (),
+                    },
+                    _ => (),
+                },
+                "score" => match value.split_once(' ') {
+                    Some((score, link)) => match score.parse::<f64>() {
+                        Ok(score) => {
+                            checkpoint.scores.insert(link.into(), score);
+                        }
+                        _ => (),
+                    },
+                    _ => (),
+                },
+                "metadata" => {
+                    let mut fields = value.splitn(3, '\t');
+
+                    match (fields.next(), fields.next(), fields.next()) {
+                        (Some(link), Some(key), Some(value)) => {
+                            checkpoint
+                                .metadata
+                                .entry(link.into())
+                                .or_default()
+                                .insert(unescape(key), unescape(value));
+                        }
+                        _ => (),
+                    }
+                }
+                "sitemap" => {
+                    checkpoint.sitemaps.insert(value.into());
+                }
+                "sitemap_visited" => {
+                    checkpoint.sitemaps_visited.insert(value.into());
+                }
+                _ => (),
+            }
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Write the checkpoint to the file. The data is written to a temporary file first and moved into place so a crash never leaves a partial checkpoint behind.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let tmp = tmp_path(path);
+
+        {
+            let mut file = std::fs::File::create(&tmp)?;
+            file.write_all(self.encode().as_bytes())?;
+            file.sync_all()?;
+        }
+
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Read a checkpoint from the file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::decode(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Stream the checkpoint records to the file from a blocking task so the crawl state is not cloned. The records are written to a temporary file moved into place once finished.
+pub struct CheckpointWriter {
+    /// The records not sent to the file yet.
+    chunk: String,
+    /// Send the records to the file.
+    sender: tokio::sync::mpsc::Sender<String>,
+    /// The task writing the file.
+    task: tokio::task::JoinHandle<Result<(), Error>>,
+}
+
+impl CheckpointWriter {
+    /// Start writing the checkpoint to the file. The links of the state are written first and the links streamed are added after.
+    pub fn new(path: PathBuf, state: &Checkpoint) -> Self {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel::<String>(4);
+
+        let task = tokio::task::spawn_blocking(move || {
+            let tmp = tmp_path(&path);
+
+            {
+                let mut file = BufWriter::new(std::fs::File::create(&tmp)?);
+
+                while let Some(chunk) = receiver.blocking_recv() {
+                    file.write_all(chunk.as_bytes())?;
+                }
+
+                file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+            }
+
+            std::fs::rename(&tmp, &path)
+        });
+
+        Self {
+            chunk: state.encode(),
+            sender,
+            task,
+        }
+    }
+
+    /// Send the records once the chunk is full.
+    async fn send(&mut self, force: bool) {
+        if force || self.chunk.len() >= CHUNK_BYTES {
+            let chunk = std::mem::replace(&mut self.chunk, String::with_capacity(CHUNK_BYTES));
+            // the error is returned by the task when the file can not be written.
+            let _ = self.sender.send(chunk).await;
+        }
+    }
+
+    /// Add a visited link.
+    pub async fn visited(&mut self, link: &CaseInsensitiveString) {
+        push_record(&mut self.chunk, "visited", link.inner());
+        self.send(false).await
+    }
+
+    /// Add a link waiting to be crawled with the link hops from the start page and the frontier score.
+    pub async fn frontier(
+        &mut self,
+        link: &CaseInsensitiveString,
+        depth: Option<usize>,
+        score: Option<f64>,
+    ) {
+        push_record(&mut self.chunk, "frontier", link.inner());
+        match depth {
+            Some(depth) => push_number(&mut self.chunk, "depth", depth, link),
+            _ => (),
+        }
+        match score {
+            Some(score) => push_number(&mut self.chunk, "score", score, link),
+            _ => (),
+        }
+        self.send(false).await
+    }
+
+    /// Add the metadata from the link hook of a link waiting to be crawled.
+    pub async fn metadata(&mut self, link: &CaseInsensitiveString, metadata: &LinkMetadata) {
+        push_metadata(&mut self.chunk, link, metadata);
+        self.send(false).await
+    }
+
+    /// Add a sitemap waiting to be crawled or crawled.
+    pub async fn sitemap(&mut self, link: &CaseInsensitiveString, visited: bool) {
+        push_record(
+            &mut self.chunk,
+            if visited {
+                "sitemap_visited"
+            } else {
+                "sitemap"
+            },
+            link.inner(),
+        );
+        self.send(false).await
+    }
+
+    /// Write the records left and move the file into place.
+    pub async fn finish(mut self) -> Result<(), Error> {
+        self.send(true).await;
+        drop(self.sender);
+
+        match self.task.await {
+            Ok(result) => result,
+            Err(e) => Err(Error::other(e)),
+        }
+    }
+}
+
+#[test]
+fn checkpoint_round_trip() {
+    let checkpoint = Checkpoint {
+        url: "https://choosealicense.com".into(),
+        status: CrawlStatus::Active,
+        visited: HashSet::from(["https://choosealicense.com".into()]),
+        frontier: HashSet::from([
+            "https://choosealicense.com/licenses/".into(),
+            "https://choosealicense.com/about/".into(),
+        ]),
+        depths: HashMap::from([("https://choosealicense.com/licenses/".into(), 1)]),
+        scores: HashMap::from([("https://choosealicense.com/licenses/".into(), 0.75)]),
+        metadata: HashMap::from([(
+            "https://choosealicense.com/about/".into(),
+            LinkMetadata::from([("label".into(), "about\tus\n\\".into())]),
+        )]),
+        sitemaps: HashSet::from(["https://choosealicense.com/sitemap.xml".into()]),
+        sitemaps_visited: HashSet::from(["https://choosealicense.com/sitemap-index.xml".into()]),
+        budget: Some(HashMap::from([("*".into(), 50), ("/licenses".into(), 4)])),
+        budget_used: Some(CrawlBudgetUsage {
+            paths: HashMap::from([
+                (
+                    "*".into(),
+                    BudgetUsage {
+                        limit: 50,
+                        used: 2,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "/licenses".into(),
+                    BudgetUsage {
+                        limit: 4,
+                        used: 2,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            patterns: HashMap::from([(
+                "^(/[^?]*) (\\d+)".into(),
+                BudgetUsage {
+                    limit: 1,
+                    used: 2,
+                    groups: HashMap::from([("/licenses/,1".into(), 1), ("/about/ x,2".into(), 1)]),
+                },
+            )]),
+            hosts: HashMap::from([(
+                "choosealicense.com".into(),
+                BudgetUsage {
+                    limit: 10,
+                    used: 2,
+                    ..Default::default()
+                },
+            )]),
+        }),
+    };
+
+    let path = std::env::temp_dir().join("spider-checkpoint-round-trip");
+
+    checkpoint.save(&path).unwrap();
+
+    let restored = Checkpoint::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(restored, checkpoint);
+}
+
+#[tokio::test]
+async fn checkpoint_writer() {
+    let path = std::env::temp_dir().join("spider-checkpoint-writer");
+    let mut writer = CheckpointWriter::new(
+        path.clone(),
+        &Checkpoint {
+            url: "https://choosealicense.com".into(),
+            ..Default::default()
+        },
+    );
+
+    for i in 0..5000 {
+        writer
+            .visited(&string_concat!("https://choosealicense.com/", i.to_string()).into())
+            .await;
+    }
+
+    let link: CaseInsensitiveString = "https://choosealicense.com/licenses/".into();
+
+    writer.frontier(&link, Some(2), Some(0.5)).await;
+    writer
+        .metadata(&link, &LinkMetadata::from([("label".into(), "mit".into())]))
+        .await;
+    writer.finish().await.unwrap();
+
+    let restored = Checkpoint::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(restored.visited.len(), 5000);
+    assert!(restored.frontier.contains(&link));
+    assert_eq!(restored.depths.get(&link), Some(&2));
+    assert_eq!(restored.scores.get(&link), Some(&0.5));
+    assert_eq!(restored.metadata[&link]["label"], "mit");
+}
+
+#[test]
+fn checkpoint_invalid_header() {
+    assert!(Checkpoint::decode("visited https://choosealicense.com").is_err());
+}
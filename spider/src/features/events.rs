@@ -0,0 +1,55 @@
+use crate::features::stats::SkipReason;
+use crate::website::CrawlStatus;
+use crate::CaseInsensitiveString;
+
+/// An event of the crawl sent to the event subscribers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrawlEvent {
+    /// A new link was found. The parent url is empty for the links found on the start page.
+    Discovered {
+        /// The url of the link.
+        url: CaseInsensitiveString,
+        /// The url of the page the link was found on.
+        parent_url: CaseInsensitiveString,
+        /// The link hops from the start page.
+        depth: usize,
+    },
+    /// A link was skipped and not crawled.
+    Skipped {
+        /// The url of the link.
+        url: CaseInsensitiveString,
+        /// The reason the link was skipped.
+        reason: SkipReason,
+    },
+    /// A page was fetched.
+    Fetched {
+        /// The url of the page.
+        url: CaseInsensitiveString,
+        /// The status code of the page.
+        status_code: u16,
+        /// The bytes of the page content.
+        bytes: usize,
+    },
+    /// A request failed with a network error or an error status code.
+    Failed {
+        /// The url of the page.
+        url: CaseInsensitiveString,
+        /// The status code of the response. The status code is `0` when no response was received.
+        status_code: u16,
+        /// The error of the request.
+        error: String,
+    },
+    /// A request was redirected.
+    Redirected {
+        /// The url requested.
+        url: CaseInsensitiveString,
+        /// The final url after the redirects.
+        to: String,
+    },
+    /// The status of the crawl changed.
+    StatusChanged {
+        /// The new status of the crawl.
+        status: CrawlStatus,
+    },
+}
@@ -0,0 +1,216 @@
+use crate::CaseInsensitiveString;
+use hashbrown::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// The amount of words in a shingle.
+const SHINGLE_SIZE: usize = 3;
+
+/// Detect pages with nearly the same content as a page crawled earlier. The links of a near-duplicate page are not followed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NearDuplicate {
+    /// The similarity between `0.0` and `1.0` of the content fingerprints to treat as a duplicate.
+    pub threshold: f64,
+}
+
+impl Default for NearDuplicate {
+    fn default() -> Self {
+        Self { threshold: 0.95 }
+    }
+}
+
+impl NearDuplicate {
+    /// Detect near-duplicates with the similarity threshold.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold: threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// The max amount of bits the fingerprints can differ by.
+    fn max_distance(&self) -> u32 {
+        ((1.0 - self.threshold.clamp(0.0, 1.0)) * 64.0).floor() as u32
+    }
+}
+
+/// The visible text of the html without the tags, scripts and styles.
+pub fn visible_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len() / 2);
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        text.push(' ');
+        rest = &rest[start..];
+
+        let skip = ["<script", "<style", "<noscript"]
+            .iter()
+            .find(|tag| rest.len() >= tag.len() && rest[..tag.len()].eq_ignore_ascii_case(tag));
+
+        match skip {
+            Some(tag) => {
+                let close = string_concat!("</", &tag[1..]);
+                rest = match rest.to_ascii_lowercase().find(close.as_str()) {
+                    Some(end) => &rest[end..],
+                    _ => "",
+                };
+            }
+            _ => (),
+        }
+
+        rest = match rest.find('>') {
+            Some(end) => &rest[end + 1..],
+            _ => "",
+        };
+    }
+
+    text.push_str(rest);
+    text
+}
+
+/// The simhash fingerprint of the text over shingles of words.
+pub fn simhash(text: &str) -> Option<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut weights = [0i64; 64];
+
+    for shingle in words.windows(SHINGLE_SIZE.min(words.len())) {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        shingle.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    Some(
+        weights
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| **w > 0)
+            .fold(0u64, |fingerprint, (bit, _)| fingerprint | 1 << bit),
+    )
+}
+
+/// The fingerprints of the pages crawled shared between the crawl tasks.
+#[derive(Debug, Default)]
+pub struct FingerprintIndex {
+    /// The max amount of bits the fingerprints can differ by.
+    max_distance: u32,
+    /// The fingerprints of the pages that were not duplicates.
+    fingerprints: Mutex<Vec<(u64, CaseInsensitiveString)>>,
+    /// The near-duplicate urls with the url of the page they duplicate.
+    duplicates: Mutex<HashMap<CaseInsensitiveString, CaseInsensitiveString>>,
+}
+
+impl FingerprintIndex {
+    /// A new index for the near duplicate detection.
+    pub fn new(near_duplicate: &NearDuplicate) -> Self {
+        Self {
+            max_distance: near_duplicate.max_distance(),
+            ..Default::default()
+        }
+    }
+
+    /// Add the fingerprint of the url returning the url of an earlier page it is a near-duplicate of.
+    pub fn insert(&self, url: &str, fingerprint: u64) -> Option<CaseInsensitiveString> {
+        let original = match self.fingerprints.lock() {
+            Ok(mut fingerprints) => {
+                let original = fingerprints
+                    .iter()
+                    .find(|(f, u)| {
+                        (f ^ fingerprint).count_ones() <= self.max_distance && u.inner() != url
+                    })
+                    .map(|(_, u)| u.clone());
+
+                if original.is_none() {
+                    fingerprints.push((fingerprint, url.into()));
+                }
+
+                original
+            }
+            _ => None,
+        };
+
+        match (&original, self.duplicates.lock()) {
+            (Some(original), Ok(mut duplicates)) => {
+                duplicates.insert(url.into(), original.clone());
+            }
+            _ => (),
+        }
+
+        original
+    }
+
+    /// The near-duplicate urls with the url of the page they duplicate.
+    pub fn duplicates(&self) -> HashMap<CaseInsensitiveString, CaseInsensitiveString> {
+        match self.duplicates.lock() {
+            Ok(duplicates) => duplicates.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Remove all the fingerprints and duplicates.
+    pub fn clear(&self) {
+        match self.fingerprints.lock() {
+            Ok(mut fingerprints) => fingerprints.clear(),
+            _ => (),
+        }
+        match self.duplicates.lock() {
+            Ok(mut duplicates) => duplicates.clear(),
+            _ => (),
+        }
+    }
+}
+
+#[test]
+fn fingerprint_near_duplicate() {
+    let page = |facet: &str| {
+        visible_text(&string_concat!(
+            "<html><head><style>body { color: red; }</style></head><body><h1>MIT License</h1><p>",
+            "A short and simple permissive license with conditions only requiring preservation ",
+            "of copyright and license notices. Licensed works, modifications, and larger works ",
+            "may be distributed under different terms and without source code.</p><p>",
+            "Permissions: commercial use, distribution, modification and private use. ",
+            "Conditions: license and copyright notice. Limitations: liability and warranty.",
+            facet,
+            "</p><script>var facet = 1;</script></body></html>"
+        ))
+    };
+
+    assert!(!page("").contains("color"));
+    assert!(!page("").contains("facet"));
+
+    let index = FingerprintIndex::new(&NearDuplicate::new(0.9));
+    let original = simhash(&page("")).unwrap();
+    let facet = simhash(&page(" Sorted by name.")).unwrap();
+    let other = simhash("The GNU General Public License is a free, copyleft license for software and other kinds of works.").unwrap();
+
+    assert_eq!(
+        index.insert("https://choosealicense.com/licenses/mit/", original),
+        None
+    );
+    assert_eq!(
+        index.insert("https://choosealicense.com/licenses/mit/?sort=name", facet),
+        Some("https://choosealicense.com/licenses/mit/".into())
+    );
+    assert_eq!(
+        index.insert("https://choosealicense.com/licenses/gpl-3.0/", other),
+        None
+    );
+    assert_eq!(index.duplicates().len(), 1);
+}
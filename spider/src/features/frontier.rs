@@ -0,0 +1,375 @@
+use crate::page::Page;
+use crate::CaseInsensitiveString;
+use compact_str::CompactString;
+use hashbrown::{HashMap, HashSet};
+use smallvec::SmallVec;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::sync::Arc;
+
+/// Score a link for the frontier using the url, the anchor text and the page the link was found on. Links with higher scores are crawled first.
+pub type FrontierScore = Arc<dyn Fn(&str, &str, &Page) -> f64 + Send + Sync>;
+
+/// The frontier score callback set on the configuration.
+#[derive(Clone)]
+pub struct FrontierScorer(pub FrontierScore);
+
+impl std::fmt::Debug for FrontierScorer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FrontierScorer")
+    }
+}
+
+/// A link waiting in the frontier.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontierLink {
+    /// The url of the link.
+    pub url: CaseInsensitiveString,
+    /// The link hops from the start page.
+    pub depth: usize,
+    /// The score of the link from the frontier score callback.
+    pub score: f64,
+}
+
+/// The links found on a crawled page.
+#[derive(Debug, Clone, Default)]
+pub struct PageLinks {
+    /// The url of the page.
+    pub url: CaseInsensitiveString,
+    /// The links found.
+    pub links: HashSet<CaseInsensitiveString>,
+    /// The link hops from the start page to the page the links were found on.
+    pub depth: usize,
+    /// The frontier scores of the links.
+    pub scores: HashMap<CaseInsensitiveString, f64>,
+    /// The urls of the links before they were normalized.
+    pub originals: HashMap<CaseInsensitiveString, String>,
+}
+
+impl PageLinks {
+    /// New page links found at the depth.
+    pub fn new(links: HashSet<CaseInsensitiveString>, depth: usize) -> Self {
+        Self {
+            links,
+            depth,
+            ..Default::default()
+        }
+    }
+
+    /// New page links found on the page with the frontier scores.
+    pub fn from_page(
+        page: &Page,
+        links: HashSet<CaseInsensitiveString>,
+        scores: HashMap<CaseInsensitiveString, f64>,
+    ) -> Self {
+        Self {
+            url: page.get_url().into(),
+            links,
+            depth: page.depth,
+            scores,
+            ..Default::default()
+        }
+    }
+}
+
+/// The order the links waiting to be crawled are taken in.
+pub trait Frontier: FrontierClone + std::fmt::Debug + Send + Sync {
+    /// Add a link waiting to be crawled.
+    fn push(&mut self, link: FrontierLink);
+    /// Take the next link to crawl.
+    fn pop(&mut self) -> Option<FrontierLink>;
+    /// The amount of links waiting.
+    fn len(&self) -> usize;
+    /// Read the links waiting one at a time.
+    fn iter(&self) -> Box<dyn Iterator<Item = FrontierLink> + Send + '_>;
+    /// The links waiting.
+    fn links(&self) -> Vec<CaseInsensitiveString> {
+        self.iter().map(|link| link.url).collect()
+    }
+    /// The max amount of links to take for a crawl round. Taking fewer links lets the links found after be ordered against the links waiting.
+    fn round_size(&self) -> usize {
+        usize::MAX
+    }
+    /// The frontier has no links waiting.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Clone the frontier into a box. This is implemented for every frontier that is `Clone`.
+pub trait FrontierClone {
+    /// Clone the frontier into a box.
+    fn clone_box(&self) -> Box<dyn Frontier>;
+}
+
+impl<T: Frontier + Clone + 'static> FrontierClone for T {
+    fn clone_box(&self) -> Box<dyn Frontier> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Frontier> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Crawl the links closest to the start page first.
+#[derive(Debug, Clone, Default)]
+pub struct BreadthFirst {
+    /// The links waiting.
+    links: VecDeque<FrontierLink>,
+}
+
+impl Frontier for BreadthFirst {
+    fn push(&mut self, link: FrontierLink) {
+        self.links.push_back(link)
+    }
+    fn pop(&mut self) -> Option<FrontierLink> {
+        self.links.pop_front()
+    }
+    fn len(&self) -> usize {
+        self.links.len()
+    }
+    fn iter(&self) -> Box<dyn Iterator<Item = FrontierLink> + Send + '_> {
+        Box::new(self.links.iter().cloned())
+    }
+}
+
+/// Crawl the links found last first going deep before wide.
+#[derive(Debug, Clone, Default)]
+pub struct DepthFirst {
+    /// The links waiting.
+    links: Vec<FrontierLink>,
+    /// The max amount of links to take for a crawl round.
+    round_size: usize,
+}
+
+impl DepthFirst {
+    /// A new depth first frontier taking the amount of links for a crawl round.
+    pub fn new(round_size: usize) -> Self {
+        Self {
+            links: Vec::new(),
+            round_size: round_size.max(1),
+        }
+    }
+}
+
+impl Frontier for DepthFirst {
+    fn push(&mut self, link: FrontierLink) {
+        self.links.push(link)
+    }
+    fn pop(&mut self) -> Option<FrontierLink> {
+        self.links.pop()
+    }
+    fn len(&self) -> usize {
+        self.links.len()
+    }
+    fn iter(&self) -> Box<dyn Iterator<Item = FrontierLink> + Send + '_> {
+        Box::new(self.links.iter().cloned())
+    }
+    fn round_size(&self) -> usize {
+        self.round_size
+    }
+}
+
+/// A link ordered by the score, the depth and the order added.
+#[derive(Debug, Clone)]
+struct ScoredLink {
+    /// The link waiting.
+    link: FrontierLink,
+    /// The order the link was added.
+    order: u64,
+}
+
+impl Ord for ScoredLink {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.link
+            .score
+            .total_cmp(&other.link.score)
+            .then_with(|| other.link.depth.cmp(&self.link.depth))
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for ScoredLink {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ScoredLink {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoredLink {}
+
+/// Crawl the links with the highest score first. Links with the same score are taken closest to the start page first.
+#[derive(Debug, Clone, Default)]
+pub struct BestFirst {
+    /// The links waiting.
+    links: BinaryHeap<ScoredLink>,
+    /// The amount of links added.
+    order: u64,
+    /// The max amount of links to take for a crawl round.
+    round_size: usize,
+}
+
+impl BestFirst {
+    /// A new best first frontier taking the amount of links for a crawl round.
+    pub fn new(round_size: usize) -> Self {
+        Self {
+            round_size: round_size.max(1),
+            ..Default::default()
+        }
+    }
+}
+
+impl Frontier for BestFirst {
+    fn push(&mut self, link: FrontierLink) {
+        self.order += 1;
+        self.links.push(ScoredLink {
+            link,
+            order: self.order,
+        })
+    }
+    fn pop(&mut self) -> Option<FrontierLink> {
+        self.links.pop().map(|l| l.link)
+    }
+    fn len(&self) -> usize {
+        self.links.len()
+    }
+    fn iter(&self) -> Box<dyn Iterator<Item = FrontierLink> + Send + '_> {
+        Box::new(self.links.iter().map(|l| l.link.clone()))
+    }
+    fn round_size(&self) -> usize {
+        self.round_size
+    }
+}
+
+/// The frontier strategy to use for the crawl.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum FrontierStrategy {
+    /// Crawl the links closest to the start page first.
+    #[default]
+    BreadthFirst,
+    /// Crawl deep before wide.
+    DepthFirst,
+    /// Crawl the links with the highest score first. Set the score with `with_frontier_score`.
+    BestFirst,
+    /// Use a custom frontier.
+    Custom(fn() -> Box<dyn Frontier>),
+}
+
+impl FrontierStrategy {
+    /// Build the frontier. The round size is the amount of links taken for each crawl round when the frontier orders across rounds.
+    pub fn build(&self, round_size: usize) -> Box<dyn Frontier> {
+        match self {
+            FrontierStrategy::BreadthFirst => Box::new(BreadthFirst::default()),
+            FrontierStrategy::DepthFirst => Box::new(DepthFirst::new(round_size)),
+            FrontierStrategy::BestFirst => Box::new(BestFirst::new(round_size)),
+            FrontierStrategy::Custom(frontier) => frontier(),
+        }
+    }
+}
+
+/// Score the links found on the page with the frontier score callback.
+pub fn score_links(
+    page: &Page,
+    links: &HashSet<CaseInsensitiveString>,
+    selectors: &(CompactString, SmallVec<[CompactString; 2]>),
+    score: &Option<FrontierScorer>,
+) -> HashMap<CaseInsensitiveString, f64> {
+    match score {
+        Some(FrontierScorer(score)) if !links.is_empty() => {
+            let anchors = page.links_anchor_text(selectors);
+
+            links
+                .iter()
+                .map(|link| {
+                    let text = match anchors.get(link) {
+                        Some(text) => text.as_str(),
+                        _ => "",
+                    };
+                    (link.clone(), score(link.inner(), text, page))
+                })
+                .collect()
+        }
+        _ => Default::default(),
+    }
+}
+
+#[test]
+fn frontier_breadth_first() {
+    let mut frontier = FrontierStrategy::BreadthFirst.build(2);
+
+    frontier.push(FrontierLink {
+        url: "https://choosealicense.com/a".into(),
+        ..Default::default()
+    });
+    frontier.push(FrontierLink {
+        url: "https://choosealicense.com/b".into(),
+        ..Default::default()
+    });
+
+    assert_eq!(frontier.round_size(), usize::MAX);
+    assert_eq!(
+        frontier.pop().map(|l| l.url),
+        Some("https://choosealicense.com/a".into())
+    );
+}
+
+#[test]
+fn frontier_depth_first() {
+    let mut frontier = FrontierStrategy::DepthFirst.build(2);
+
+    frontier.push(FrontierLink {
+        url: "https://choosealicense.com/a".into(),
+        depth: 1,
+        ..Default::default()
+    });
+    frontier.push(FrontierLink {
+        url: "https://choosealicense.com/a/b".into(),
+        depth: 2,
+        ..Default::default()
+    });
+
+    assert_eq!(frontier.round_size(), 2);
+    assert_eq!(
+        frontier.pop().map(|l| l.url),
+        Some("https://choosealicense.com/a/b".into())
+    );
+}
+
+#[test]
+fn frontier_best_first() {
+    let mut frontier = FrontierStrategy::BestFirst.build(2);
+
+    for (url, depth, score) in [
+        ("https://choosealicense.com/tags/mit", 1, 0.0),
+        ("https://choosealicense.com/licenses/mit", 2, 1.0),
+        ("https://choosealicense.com/tags/gpl", 1, 0.0),
+        ("https://choosealicense.com/about", 2, 0.0),
+    ] {
+        frontier.push(FrontierLink {
+            url: url.into(),
+            depth,
+            score,
+        });
+    }
+
+    let order: Vec<CaseInsensitiveString> =
+        std::iter::from_fn(|| frontier.pop().map(|l| l.url)).collect();
+
+    assert_eq!(
+        order,
+        vec![
+            "https://choosealicense.com/licenses/mit".into(),
+            "https://choosealicense.com/tags/mit".into(),
+            "https://choosealicense.com/tags/gpl".into(),
+            "https://choosealicense.com/about".into(),
+        ]
+    );
+}
@@ -0,0 +1,314 @@
+use crate::CaseInsensitiveString;
+use hashbrown::HashMap;
+use reqwest::header::{HeaderMap, ETAG, LAST_MODIFIED};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Recrawl only the pages that changed since the last crawl with conditional requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IncrementalCrawl {
+    /// The file to keep the validators in between runs. The validators are only kept in memory for the website when not set.
+    pub store_path: Option<String>,
+    /// Follow the links of unchanged pages using the links found the last time the page changed.
+    pub follow_unchanged: bool,
+}
+
+impl IncrementalCrawl {
+    /// Recrawl incrementally keeping the validators in the file.
+    pub fn new(store_path: Option<String>) -> Self {
+        Self {
+            store_path,
+            ..Default::default()
+        }
+    }
+}
+
+/// The validators of a page response used to check if the page changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Validators {
+    /// The ETag header sent back with `If-None-Match`.
+    pub etag: Option<String>,
+    /// The Last-Modified header sent back with `If-Modified-Since`.
+    pub last_modified: Option<String>,
+    /// The hash of the page content.
+    pub content_hash: Option<u64>,
+}
+
+impl Validators {
+    /// The validators of the response headers.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+
+        if etag.is_none() && last_modified.is_none() {
+            None
+        } else {
+            Some(Self {
+                etag,
+                last_modified,
+                content_hash: None,
+            })
+        }
+    }
+}
+
+/// The hash of the page content.
+pub fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A stored page with the validators and the links found.
+#[derive(Debug, Clone, Default)]
+struct StoredPage {
+    /// The validators of the last response.
+    validators: Validators,
+    /// The links found the last time the page changed.
+    links: Vec<CaseInsensitiveString>,
+}
+
+/// The validators of the pages crawled shared between the crawl tasks and kept between runs.
+#[derive(Debug, Default)]
+pub struct ValidatorStore {
+    /// The pages stored by url.
+    pages: Mutex<HashMap<CaseInsensitiveString, StoredPage>>,
+}
+
+/// Escape the field for the store file.
+fn escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace(' ', "\\s")
+}
+
+/// Unescape the field of the store file.
+fn unescape(field: &str) -> String {
+    let mut value = String::with_capacity(field.len());
+    let mut chars = field.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => value.push('\t'),
+                Some('n') => value.push('\n'),
+                Some('s') => value.push(' '),
+                Some(c) => value.push(c),
+                _ => (),
+            }
+        } else {
+            value.push(c);
+        }
+    }
+
+    value
+}
+
+/// The optional field of the store file.
+fn optional(field: Option<&str>) -> Option<String> {
+    match field {
+        Some(f) if !f.is_empty() => Some(unescape(f)),
+        _ => None,
+    }
+}
+
+impl ValidatorStore {
+    /// A new empty store.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Load the store from the file. A missing or unreadable file starts an empty store.
+    pub fn load(path: &str) -> Self {
+        let store = Self::new();
+
+        match std::fs::read_to_string(path) {
+            Ok(file) => match store.pages.lock() {
+                Ok(mut pages) => {
+                    for line in file.lines() {
+                        let mut fields = line.split('\t');
+
+                        let url = match fields.next() {
+                            Some(url) if !url.is_empty() => unescape(url),
+                            _ => continue,
+                        };
+                        let etag = optional(fields.next());
+                        let last_modified = optional(fields.next());
+                        let content_hash = fields.next().and_then(|h| h.parse().ok());
+                        let links = fields
+                            .next()
+                            .map(|l| {
+                                l.split(' ')
+                                    .filter(|l| !l.is_empty())
+                                    .map(|l| unescape(l).into())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+
+                        pages.insert(
+                            url.into(),
+                            StoredPage {
+                                validators: Validators {
+                                    etag,
+                                    last_modified,
+                                    content_hash,
+                                },
+                                links,
+                            },
+                        );
+                    }
+                }
+                _ => (),
+            },
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                log::warn!("unable to read the validator store {path}: {e}");
+            }
+            _ => (),
+        }
+
+        store
+    }
+
+    /// Save the store to the file.
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let mut file = String::new();
+
+        match self.pages.lock() {
+            Ok(pages) => {
+                for (url, page) in pages.iter() {
+                    let links: Vec<String> = page.links.iter().map(|l| escape(l.inner())).collect();
+
+                    file.push_str(&escape(url.inner()));
+                    file.push('\t');
+                    file.push_str(&escape(page.validators.etag.as_deref().unwrap_or_default()));
+                    file.push('\t');
+                    file.push_str(&escape(
+                        page.validators.last_modified.as_deref().unwrap_or_default(),
+                    ));
+                    file.push('\t');
+                    match page.validators.content_hash {
+                        Some(hash) => file.push_str(&hash.to_string()),
+                        _ => (),
+                    }
+                    file.push('\t');
+                    file.push_str(&links.join(" "));
+                    file.push('\n');
+                }
+            }
+            _ => (),
+        }
+
+        std::fs::write(path, file)
+    }
+
+    /// The validators stored for the url.
+    pub fn get(&self, url: &str) -> Option<Validators> {
+        match self.pages.lock() {
+            Ok(pages) => pages
+                .get(&CaseInsensitiveString::from(url))
+                .map(|p| p.validators.clone()),
+            _ => None,
+        }
+    }
+
+    /// Store the validators of the url.
+    pub fn insert(&self, url: &str, validators: Validators) {
+        match self.pages.lock() {
+            Ok(mut pages) => {
+                pages.entry(url.into()).or_default().validators = validators;
+            }
+            _ => (),
+        }
+    }
+
+    /// The links found the last time the page changed.
+    pub fn links(&self, url: &str) -> Vec<CaseInsensitiveString> {
+        match self.pages.lock() {
+            Ok(pages) => pages
+                .get(&CaseInsensitiveString::from(url))
+                .map(|p| p.links.clone())
+                .unwrap_or_default(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Store the links found on the page.
+    pub fn set_links<'a>(&self, url: &str, links: impl Iterator<Item = &'a CaseInsensitiveString>) {
+        match self.pages.lock() {
+            Ok(mut pages) => {
+                pages.entry(url.into()).or_default().links = links.cloned().collect();
+            }
+            _ => (),
+        }
+    }
+
+    /// The amount of pages stored.
+    pub fn len(&self) -> usize {
+        match self.pages.lock() {
+            Ok(pages) => pages.len(),
+            _ => 0,
+        }
+    }
+
+    /// The store has no pages.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[test]
+fn validator_store_roundtrip() {
+    let path = std::env::temp_dir().join("spider_validator_store_test");
+    let path = path.to_str().unwrap_or_default();
+    let store = ValidatorStore::new();
+    let validators = Validators {
+        etag: Some("W/\"5f\tb\"".into()),
+        last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".into()),
+        content_hash: Some(content_hash(b"<html></html>")),
+    };
+
+    store.insert("https://choosealicense.com/", validators.clone());
+    store.set_links(
+        "https://choosealicense.com/",
+        [CaseInsensitiveString::from(
+            "https://choosealicense.com/about/",
+        )]
+        .iter(),
+    );
+    store.insert(
+        "https://choosealicense.com/licenses/",
+        Validators {
+            content_hash: Some(1),
+            ..Default::default()
+        },
+    );
+    assert!(store.save(path).is_ok());
+
+    let store = ValidatorStore::load(path);
+    let _ = std::fs::remove_file(path);
+
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.get("https://choosealicense.com/"), Some(validators));
+    assert_eq!(
+        store.links("https://choosealicense.com/"),
+        vec![CaseInsensitiveString::from(
+            "https://choosealicense.com/about/"
+        )]
+    );
+    assert_eq!(
+        store
+            .get("https://choosealicense.com/licenses/")
+            .and_then(|v| v.etag),
+        None
+    );
+}
@@ -0,0 +1,322 @@
+use crate::features::frontier::PageLinks;
+use crate::page::Page;
+use crate::CaseInsensitiveString;
+use compact_str::CompactString;
+use hashbrown::HashSet;
+use smallvec::SmallVec;
+
+/// A link from a page to another page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkEdge {
+    /// The url of the page the link was found on.
+    pub source: CaseInsensitiveString,
+    /// The url of the link.
+    pub target: CaseInsensitiveString,
+    /// The text of the anchor of the link.
+    pub anchor_text: String,
+    /// The rel attribute values of the anchor of the link in lowercase.
+    pub rel: Vec<String>,
+    /// The anchor has `rel="nofollow"`.
+    pub nofollow: bool,
+}
+
+/// The links between the pages found during the crawl.
+#[derive(Debug, Clone, Default)]
+pub struct LinkGraph {
+    /// The edges in the order found.
+    edges: Vec<LinkEdge>,
+    /// The source and target of the edges added.
+    seen: HashSet<(CaseInsensitiveString, CaseInsensitiveString)>,
+}
+
+/// Escape the field for a CSV row.
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        string_concat!("\"", field.replace('"', "\"\""), "\"")
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape the text for XML.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Escape the text for a DOT quoted string.
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl LinkGraph {
+    /// A new empty graph.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add the edge. Only the first edge between the same pages is kept.
+    pub fn insert(&mut self, edge: LinkEdge) -> bool {
+        if edge.source == edge.target
+            || !self.seen.insert((edge.source.clone(), edge.target.clone()))
+        {
+            false
+        } else {
+            self.edges.push(edge);
+            true
+        }
+    }
+
+    /// The edges in the order found.
+    pub fn edges(&self) -> &[LinkEdge] {
+        &self.edges
+    }
+
+    /// The urls of the pages in the order found.
+    pub fn nodes(&self) -> Vec<&CaseInsensitiveString> {
+        let mut seen = HashSet::new();
+        let mut nodes = Vec::new();
+
+        for edge in self.edges.iter() {
+            for node in [&edge.source, &edge.target] {
+                if seen.insert(node) {
+                    nodes.push(node);
+                }
+            }
+        }
+
+        nodes
+    }
+
+    /// The amount of edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The graph has no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Remove all the edges.
+    pub fn clear(&mut self) {
+        self.edges.clear();
+        self.seen.clear();
+    }
+
+    /// Export the edges as a CSV edge list with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("source,target,anchor_text,rel,nofollow\n");
+
+        for edge in self.edges.iter() {
+            csv.push_str(&escape_csv(edge.source.inner()));
+            csv.push(',');
+            csv.push_str(&escape_csv(edge.target.inner()));
+            csv.push(',');
+            csv.push_str(&escape_csv(&edge.anchor_text));
+            csv.push(',');
+            csv.push_str(&escape_csv(&edge.rel.join(" ")));
+            csv.push(',');
+            csv.push_str(if edge.nofollow { "true" } else { "false" });
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Export the graph as GraphML with the anchor text, rel and nofollow as edge data.
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"anchor_text\" for=\"edge\" attr.name=\"anchor_text\" attr.type=\"string\"/>\n",
+            "  <key id=\"rel\" for=\"edge\" attr.name=\"rel\" attr.type=\"string\"/>\n",
+            "  <key id=\"nofollow\" for=\"edge\" attr.name=\"nofollow\" attr.type=\"boolean\"/>\n",
+            "  <graph id=\"links\" edgedefault=\"directed\">\n"
+        ));
+
+        for node in self.nodes() {
+            xml.push_str(&string_concat!(
+                "    <node id=\"",
+                escape_xml(node.inner()),
+                "\"/>\n"
+            ));
+        }
+
+        for edge in self.edges.iter() {
+            xml.push_str(&string_concat!(
+                "    <edge source=\"",
+                escape_xml(edge.source.inner()),
+                "\" target=\"",
+                escape_xml(edge.target.inner()),
+                "\">\n      <data key=\"anchor_text\">",
+                escape_xml(&edge.anchor_text),
+                "</data>\n      <data key=\"rel\">",
+                escape_xml(&edge.rel.join(" ")),
+                "</data>\n      <data key=\"nofollow\">",
+                if edge.nofollow { "true" } else { "false" },
+                "</data>\n    </edge>\n"
+            ));
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    /// Export the graph as a DOT digraph labeling the edges with the anchor text. Nofollow links are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph links {\n");
+
+        for edge in self.edges.iter() {
+            dot.push_str(&string_concat!(
+                "  \"",
+                escape_dot(edge.source.inner()),
+                "\" -> \"",
+                escape_dot(edge.target.inner()),
+                "\" [label=\"",
+                escape_dot(&edge.anchor_text),
+                if edge.nofollow {
+                    "\", style=dashed];\n"
+                } else {
+                    "\"];\n"
+                }
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Extend<LinkEdge> for LinkGraph {
+    fn extend<T: IntoIterator<Item = LinkEdge>>(&mut self, edges: T) {
+        for edge in edges {
+            self.insert(edge);
+        }
+    }
+}
+
+/// The edges from the page to the links found with the anchor text and rel of the links.
+pub fn link_edges(
+    page: &Page,
+    page_links: &PageLinks,
+    selectors: &(CompactString, SmallVec<[CompactString; 2]>),
+) -> Vec<LinkEdge> {
+    let mut anchors = page.links_anchors(selectors);
+    let source = &page_links.url;
+
+    page_links
+        .links
+        .iter()
+        .map(|link| {
+            let anchor = anchors.remove(link).unwrap_or_default();
+
+            LinkEdge {
+                source: source.clone(),
+                target: link.clone(),
+                nofollow: anchor.rel.iter().any(|r| r == "nofollow"),
+                anchor_text: anchor.text,
+                rel: anchor.rel,
+            }
+        })
+        .collect()
+}
+
+/// The edges to the links found on the page when the link graph is recorded.
+pub fn record_link_edges(
+    link_graph: bool,
+    page: &Page,
+    page_links: &PageLinks,
+    selectors: &(CompactString, SmallVec<[CompactString; 2]>),
+) -> Vec<LinkEdge> {
+    if link_graph && !page_links.links.is_empty() {
+        link_edges(page, page_links, selectors)
+    } else {
+        Vec::new()
+    }
+}
+
+#[test]
+fn link_graph_export() {
+    let mut graph = LinkGraph::new();
+
+    graph.extend([
+        LinkEdge {
+            source: "https://choosealicense.com/".into(),
+            target: "https://choosealicense.com/licenses/".into(),
+            anchor_text: "Licenses, \"all\"".into(),
+            ..Default::default()
+        },
+        LinkEdge {
+            source: "https://choosealicense.com/".into(),
+            target: "https://choosealicense.com/about/".into(),
+            anchor_text: "About".into(),
+            rel: vec!["nofollow".into()],
+            nofollow: true,
+        },
+        LinkEdge {
+            source: "https://choosealicense.com/".into(),
+            target: "https://choosealicense.com/licenses/".into(),
+            anchor_text: "Licenses again".into(),
+            ..Default::default()
+        },
+    ]);
+
+    assert_eq!(graph.len(), 2);
+    assert_eq!(graph.nodes().len(), 3);
+    assert_eq!(
+        graph.to_csv(),
+        concat!(
+            "source,target,anchor_text,rel,nofollow\n",
+            "https://choosealicense.com/,https://choosealicense.com/licenses/,\"Licenses, \"\"all\"\"\",,false\n",
+            "https://choosealicense.com/,https://choosealicense.com/about/,About,nofollow,true\n"
+        )
+    );
+    assert_eq!(
+        graph.to_dot(),
+        concat!(
+            "digraph links {\n",
+            "  \"https://choosealicense.com/\" -> \"https://choosealicense.com/licenses/\" [label=\"Licenses, \\\"all\\\"\"];\n",
+            "  \"https://choosealicense.com/\" -> \"https://choosealicense.com/about/\" [label=\"About\", style=dashed];\n",
+            "}\n"
+        )
+    );
+
+    let graphml = graph.to_graphml();
+
+    assert!(graphml.contains("<node id=\"https://choosealicense.com/about/\"/>"));
+    assert!(graphml.contains("<data key=\"anchor_text\">Licenses, &quot;all&quot;</data>"));
+    assert!(graphml.contains("<data key=\"nofollow\">true</data>"));
+}
+
+#[test]
+fn link_graph_source() {
+    let page = crate::page::build("", Default::default());
+    let mut page_links = PageLinks::new(
+        HashSet::from(["https://choosealicense.com/about/".into()]),
+        0,
+    );
+    page_links.url = "https://choosealicense.com/".into();
+
+    let edges = record_link_edges(
+        true,
+        &page,
+        &page_links,
+        &(Default::default(), Default::default()),
+    );
+
+    assert_eq!(
+        edges,
+        vec![LinkEdge {
+            source: "https://choosealicense.com/".into(),
+            target: "https://choosealicense.com/about/".into(),
+            ..Default::default()
+        }]
+    );
+}
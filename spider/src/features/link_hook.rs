@@ -0,0 +1,249 @@
+use crate::features::frontier::PageLinks;
+use crate::page::Page;
+use crate::CaseInsensitiveString;
+use compact_str::CompactString;
+use hashbrown::{HashMap, HashSet};
+use smallvec::SmallVec;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The metadata attached to a link by the link hook. The metadata is set on the page fetched for the link.
+pub type LinkMetadata = HashMap<String, String>;
+
+/// A link found on a page passed to the link hook.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkContext {
+    /// The url of the link.
+    pub url: CaseInsensitiveString,
+    /// The url of the page the link was found on.
+    pub parent_url: String,
+    /// The text of the anchor of the link.
+    pub anchor_text: String,
+    /// The rel attribute values of the anchor of the link in lowercase.
+    pub rel: Vec<String>,
+    /// The link hops from the start page to the link.
+    pub depth: usize,
+}
+
+/// The decision of the link hook for a link.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkDecision {
+    /// Crawl this url instead of the link.
+    pub url: Option<CaseInsensitiveString>,
+    /// Reject the link with the reason.
+    pub reject: Option<String>,
+    /// The priority of the link used as the frontier score.
+    pub priority: Option<f64>,
+    /// The metadata set on the page fetched for the link.
+    pub metadata: Option<LinkMetadata>,
+}
+
+impl LinkDecision {
+    /// Follow the link.
+    pub fn follow() -> Self {
+        Default::default()
+    }
+
+    /// Follow the url instead of the link.
+    pub fn rewrite(url: impl Into<CaseInsensitiveString>) -> Self {
+        Self {
+            url: Some(url.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Reject the link with the reason.
+    pub fn reject(reason: impl Into<String>) -> Self {
+        Self {
+            reject: Some(reason.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the priority of the link used as the frontier score.
+    pub fn with_priority(mut self, priority: f64) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Set the metadata that travels with the link to the page fetched.
+    pub fn with_metadata(mut self, metadata: LinkMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+}
+
+/// The links the link hook rejected and the metadata it attached to the links found on a page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkHookResult {
+    /// The metadata the link hook attached to the links.
+    pub metadata: HashMap<CaseInsensitiveString, LinkMetadata>,
+    /// The links the link hook rejected with the reason.
+    pub rejected: Vec<(CaseInsensitiveString, String)>,
+}
+
+/// The future returned from the link hook.
+pub type LinkHookFuture = Pin<Box<dyn Future<Output = LinkDecision> + Send>>;
+
+/// An async hook called for every link found on a page before it is added to the crawl.
+#[derive(Clone)]
+pub struct LinkHook(Arc<dyn Fn(LinkContext) -> LinkHookFuture + Send + Sync>);
+
+impl LinkHook {
+    /// A new link hook from the async closure.
+    pub fn new<F, Fut>(hook: F) -> Self
+    where
+        F: Fn(LinkContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = LinkDecision> + Send + 'static,
+    {
+        Self(Arc::new(move |link| Box::pin(hook(link))))
+    }
+
+    /// Call the hook for the link.
+    pub async fn call(&self, link: LinkContext) -> LinkDecision {
+        (self.0)(link).await
+    }
+}
+
+impl std::fmt::Debug for LinkHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LinkHook")
+    }
+}
+
+/// Run the link hook for the links found on the page. Rewritten links replace the links and the priority is kept as the score of the url to crawl. The rejected links and the metadata are returned.
+pub async fn apply_link_hook(
+    link_hook: &Option<LinkHook>,
+    page: &Page,
+    page_links: &mut PageLinks,
+    selectors: &(CompactString, SmallVec<[CompactString; 2]>),
+) -> LinkHookResult {
+    let mut result = LinkHookResult::default();
+
+    match link_hook {
+        Some(hook) if !page_links.links.is_empty() => {
+            let mut anchors = page.links_anchors(selectors);
+            let mut links = HashSet::with_capacity(page_links.links.len());
+            let depth = page_links.depth.saturating_add(1);
+
+            for link in page_links.links.drain() {
+                let anchor = anchors.remove(&link).unwrap_or_default();
+                let decision = hook
+                    .call(LinkContext {
+                        url: link.clone(),
+                        parent_url: page_links.url.inner().to_string(),
+                        anchor_text: anchor.text,
+                        rel: anchor.rel,
+                        depth,
+                    })
+                    .await;
+
+                match decision.reject {
+                    Some(reason) => {
+                        page_links.scores.remove(&link);
+                        result.rejected.push((link, reason));
+                        continue;
+                    }
+                    _ => (),
+                }
+
+                let url = match decision.url {
+                    Some(url) => {
+                        match page_links.scores.remove(&link) {
+                            Some(score) => {
+                                page_links.scores.insert(url.clone(), score);
+                            }
+                            _ => (),
+                        }
+                        url
+                    }
+                    _ => link,
+                };
+
+                match decision.priority {
+                    Some(priority) => {
+                        page_links.scores.insert(url.clone(), priority);
+                    }
+                    _ => (),
+                }
+
+                match decision.metadata {
+                    Some(metadata) => {
+                        result.metadata.insert(url.clone(), metadata);
+                    }
+                    _ => (),
+                }
+
+                links.insert(url);
+            }
+
+            page_links.links = links;
+        }
+        _ => (),
+    }
+
+    result
+}
+
+#[cfg(not(feature = "decentralized"))]
+#[tokio::test]
+async fn link_hook_decisions() {
+    let page = crate::page::build(
+        "https://choosealicense.com/",
+        crate::utils::PageResponse {
+            content: Some(
+                concat!(
+                    "<html><body>",
+                    "<a href=\"/licenses/mit/\">MIT License</a>",
+                    "<a href=\"/licenses/unlicense/\" rel=\"Nofollow sponsored\">Unlicense</a>",
+                    "<a href=\"/about/?ref=nav\">About</a>",
+                    "</body></html>"
+                )
+                .into(),
+            ),
+            ..Default::default()
+        },
+    );
+    let selectors = crate::page::get_page_selectors("https://choosealicense.com/", false, false)
+        .unwrap_or_default();
+    let links = page.links(&selectors).await;
+    let mut page_links = PageLinks::new(links, 1);
+
+    let hook = Some(LinkHook::new(|link: LinkContext| async move {
+        if link.rel.iter().any(|r| r == "nofollow") {
+            LinkDecision::reject("nofollow")
+        } else if link.url.inner().contains("?ref=") {
+            LinkDecision::rewrite("https://choosealicense.com/about/")
+        } else {
+            LinkDecision::follow()
+                .with_priority(link.depth as f64)
+                .with_metadata(HashMap::from([("anchor".into(), link.anchor_text)]))
+        }
+    }));
+
+    let result = apply_link_hook(&hook, &page, &mut page_links, &selectors).await;
+
+    let mit: CaseInsensitiveString = "https://choosealicense.com/licenses/mit/".into();
+
+    assert_eq!(page_links.links.len(), 2);
+    assert!(page_links.links.contains(&CaseInsensitiveString::from(
+        "https://choosealicense.com/about/"
+    )));
+    assert_eq!(
+        result.rejected,
+        vec![(
+            "https://choosealicense.com/licenses/unlicense/".into(),
+            "nofollow".to_string()
+        )]
+    );
+    assert_eq!(page_links.scores.get(&mit), Some(&2.0));
+    assert_eq!(
+        result
+            .metadata
+            .get(&mit)
+            .and_then(|m| m.get("anchor"))
+            .map(|a| a.as_str()),
+        Some("MIT License")
+    );
+}
@@ -1,3 +1,7 @@
+/// Crawl budgets by url pattern and host
+pub mod budget;
+/// Checkpoint and restore the crawl state
+pub mod checkpoint;
 /// Chrome utils
 #[cfg(feature = "chrome")]
 pub mod chrome;
@@ -6,13 +10,41 @@ pub mod chrome_common;
 /// Decentralized header handling
 #[cfg(feature = "decentralized_headers")]
 pub mod decentralized_headers;
+/// Typed events of the crawl
+pub mod events;
+/// Content fingerprints to detect near-duplicate pages
+pub mod fingerprint;
+/// The order links are crawled in
+pub mod frontier;
 /// URL globbing
 #[cfg(feature = "glob")]
 pub mod glob;
+/// Incremental recrawls with conditional requests
+pub mod incremental;
+/// Record the links between the pages
+pub mod link_graph;
+/// Async hook for the links found
+pub mod link_hook;
+/// Normalize the urls found before deduplication
+pub mod normalize;
 /// OpenAI
 #[cfg(feature = "openai")]
 pub mod openai;
 /// Common modules for OpenAI
 pub mod openai_common;
+/// Per host politeness scheduling
+pub mod politeness;
+/// Filter the responses before the body is downloaded
+pub mod response_filter;
+/// Retry transient request failures
+pub mod retry;
+/// Meta robots tags, X-Robots-Tag headers and rel=nofollow
+pub mod robots_meta;
 /// Spoof the refereer
 pub mod spoof_referrer;
+/// Live crawl statistics
+pub mod stats;
+/// Keep the visited links and the frontier on disk
+pub mod storage;
+/// Crawler trap detection
+pub mod trap;
@@ -0,0 +1,200 @@
+use url::Url;
+
+/// The query params used for tracking that are removed with `strip_tracking_params`. Params starting with `utm_` are always treated as tracking.
+const TRACKING_PARAMS: &[&str] = &[
+    "gclid",
+    "gclsrc",
+    "dclid",
+    "fbclid",
+    "msclkid",
+    "yclid",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "_ga",
+    "_gl",
+    "_hsenc",
+    "_hsmi",
+    "mkt_tok",
+    "vero_id",
+    "oly_anon_id",
+    "oly_enc_id",
+];
+
+/// The index files folded into the directory with `fold_index`.
+const INDEX_FILES: &[&str] = &[
+    "index.html",
+    "index.htm",
+    "index.php",
+    "index.asp",
+    "index.aspx",
+    "default.html",
+    "default.htm",
+    "default.asp",
+    "default.aspx",
+];
+
+/// Normalize the urls found before they are deduplicated so variants of the same page are only crawled once. Default ports are always dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UrlNormalization {
+    /// Remove the tracking params like `utm_source`, `gclid` and `fbclid`.
+    pub strip_tracking_params: bool,
+    /// Sort the query params by key.
+    pub sort_query: bool,
+    /// Collapse repeated slashes in the path.
+    pub collapse_slashes: bool,
+    /// Remove the trailing slash from the path.
+    pub strip_trailing_slash: bool,
+    /// Remove index files like `index.html` from the end of the path.
+    pub fold_index: bool,
+    /// Only keep the query params in the list.
+    pub allow_params: Option<Vec<String>>,
+    /// Remove the query params in the list.
+    pub deny_params: Vec<String>,
+}
+
+impl Default for UrlNormalization {
+    fn default() -> Self {
+        Self {
+            strip_tracking_params: true,
+            sort_query: true,
+            collapse_slashes: true,
+            strip_trailing_slash: false,
+            fold_index: false,
+            allow_params: None,
+            deny_params: Vec::new(),
+        }
+    }
+}
+
+impl UrlNormalization {
+    /// The query param is kept.
+    fn keep_param(&self, key: &str) -> bool {
+        match &self.allow_params {
+            Some(allow) if !allow.iter().any(|p| p == key) => return false,
+            _ => (),
+        }
+
+        !(self.deny_params.iter().any(|p| p == key)
+            || self.strip_tracking_params
+                && (key.starts_with("utm_") || TRACKING_PARAMS.contains(&key)))
+    }
+
+    /// Normalize the url in place.
+    pub fn normalize(&self, url: &mut Url) {
+        if url.port().is_some() && url.port() == default_port(url.scheme()) {
+            let _ = url.set_port(None);
+        }
+
+        if self.collapse_slashes || self.fold_index || self.strip_trailing_slash {
+            let mut path = url.path().to_string();
+
+            if self.collapse_slashes {
+                while path.contains("//") {
+                    path = path.replace("//", "/");
+                }
+            }
+
+            if self.fold_index {
+                match path.rfind('/') {
+                    Some(position)
+                        if INDEX_FILES.contains(&path[position + 1..].to_lowercase().as_str()) =>
+                    {
+                        path.truncate(position + 1);
+                    }
+                    _ => (),
+                }
+            }
+
+            if self.strip_trailing_slash {
+                while path.len() > 1 && path.ends_with('/') {
+                    path.pop();
+                }
+            }
+
+            if path != url.path() {
+                url.set_path(&path);
+            }
+        }
+
+        if url.query().is_some() {
+            let pairs: Vec<(String, String)> = url
+                .query_pairs()
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect();
+
+            let mut kept: Vec<(String, String)> = pairs
+                .iter()
+                .filter(|(k, _)| self.keep_param(k))
+                .cloned()
+                .collect();
+
+            if self.sort_query {
+                kept.sort_by(|a, b| a.0.cmp(&b.0));
+            }
+
+            if kept.is_empty() {
+                url.set_query(None);
+            } else if kept != pairs {
+                url.query_pairs_mut().clear().extend_pairs(kept);
+            }
+        }
+    }
+
+    /// Normalize the url returning the normalized url.
+    pub fn normalize_str(&self, url: &str) -> Option<String> {
+        match Url::parse(url) {
+            Ok(mut u) => {
+                self.normalize(&mut u);
+                Some(u.as_str().to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The default port for the scheme.
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        _ => None,
+    }
+}
+
+#[test]
+fn normalize_url() {
+    let normalization = UrlNormalization {
+        strip_trailing_slash: true,
+        fold_index: true,
+        deny_params: vec!["session".into()],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        normalization
+            .normalize_str("https://choosealicense.com:443//licenses//index.html?b=2&utm_source=x&a=1&session=9")
+            .as_deref(),
+        Some("https://choosealicense.com/licenses?a=1&b=2")
+    );
+    assert_eq!(
+        normalization
+            .normalize_str("https://choosealicense.com/about/?fbclid=1")
+            .as_deref(),
+        Some("https://choosealicense.com/about")
+    );
+
+    let normalization = UrlNormalization {
+        allow_params: Some(vec!["page".into()]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        normalization
+            .normalize_str("https://choosealicense.com/licenses/?sort=asc&page=2")
+            .as_deref(),
+        Some("https://choosealicense.com/licenses/?page=2")
+    );
+}
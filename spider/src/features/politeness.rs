@@ -0,0 +1,525 @@
+use crate::CaseInsensitiveString;
+use hashbrown::HashMap;
+use reqwest::StatusCode;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+use url::Url;
+
+/// Per host politeness for multi domain crawls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HostPoliteness {
+    /// The max amount of requests to run at once for a host.
+    pub concurrency: usize,
+    /// The minimum time between requests to the same host. The crawl delay is used if not set. A robots `Crawl-delay` overrides the delay for the host.
+    pub delay: Option<Duration>,
+}
+
+impl HostPoliteness {
+    /// New per host politeness with the max concurrent requests and the delay between requests to a host.
+    pub fn new(concurrency: usize, delay: Option<Duration>) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            delay,
+        }
+    }
+}
+
+/// Slow a host down on rate limit responses or rising latency and speed back up once the responses are healthy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveThrottle {
+    /// The delay added between requests on the first rate limit response. The delay doubles on each rate limit response after.
+    pub initial_delay: Duration,
+    /// The max delay between requests to a host while backing off. The Retry-After header is capped to the delay.
+    pub max_delay: Duration,
+    /// Slow the host down when a request takes longer than the average for the host times the factor. Set to 0 to ignore the latency.
+    pub latency_factor: f64,
+    /// The amount of times a url is crawled again after a rate limit response.
+    pub max_requeue: u32,
+}
+
+impl Default for AdaptiveThrottle {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+            latency_factor: 3.0,
+            max_requeue: 3,
+        }
+    }
+}
+
+impl AdaptiveThrottle {
+    /// Increase the delay for a host backing off.
+    fn slow_down(&self, delay: Duration) -> Duration {
+        (delay * 2).max(self.initial_delay).min(self.max_delay)
+    }
+
+    /// Decrease the delay for a host with healthy responses.
+    fn speed_up(&self, delay: Duration) -> Duration {
+        let delay = delay * 3 / 4;
+
+        if delay < Duration::from_millis(10) {
+            Duration::ZERO
+        } else {
+            delay
+        }
+    }
+}
+
+/// The response is asking the crawl to slow down.
+pub fn is_rate_limited(status_code: StatusCode) -> bool {
+    status_code == StatusCode::TOO_MANY_REQUESTS || status_code == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// The adaptive delay state for a host.
+#[derive(Debug, Default)]
+struct Backoff {
+    /// The delay added between requests.
+    delay: Duration,
+    /// No requests are sent before the time requested with the Retry-After header.
+    until: Option<Instant>,
+    /// The average time a request takes.
+    latency: Option<Duration>,
+}
+
+impl Backoff {
+    /// Add the latency of a request to the average returning true if the request took longer than the average times the factor.
+    fn record_latency(&mut self, latency: Duration, factor: f64) -> bool {
+        match self.latency {
+            Some(average) => {
+                self.latency = Some(average.mul_f64(0.8) + latency.mul_f64(0.2));
+                latency > average.mul_f64(factor)
+            }
+            _ => {
+                self.latency = Some(latency);
+                false
+            }
+        }
+    }
+
+    /// The host is slowed down.
+    fn active(&self) -> bool {
+        !self.delay.is_zero()
+            || match self.until {
+                Some(until) => until > Instant::now(),
+                _ => false,
+            }
+    }
+}
+
+/// A token bucket for the robots `Request-rate` of a host. The bucket holds the requests allowed in the period and refills over the period.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The max amount of tokens.
+    capacity: f64,
+    /// The tokens left. The tokens go below zero for the requests reserved ahead.
+    tokens: f64,
+    /// The tokens added each second.
+    refill: f64,
+    /// The time the tokens were last refilled.
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket allowing the amount of requests in the period.
+    fn new(requests: usize, period: Duration) -> Self {
+        let capacity = requests.max(1) as f64;
+
+        Self {
+            capacity,
+            tokens: capacity,
+            refill: capacity / period.as_secs_f64().max(0.001),
+            updated: Instant::now(),
+        }
+    }
+
+    /// Reserve a token for a request starting at the time returning the time the token is available.
+    fn reserve(&mut self, at: Instant) -> Instant {
+        if at > self.updated {
+            self.tokens =
+                (self.tokens + (at - self.updated).as_secs_f64() * self.refill).min(self.capacity);
+            self.updated = at;
+        }
+
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            at
+        } else {
+            (self.updated + Duration::from_secs_f64(-self.tokens / self.refill)).max(at)
+        }
+    }
+}
+
+/// The queue of requests for a host.
+#[derive(Debug)]
+struct HostQueue {
+    /// The permits for the requests running at once.
+    semaphore: Arc<Semaphore>,
+    /// The delay between requests overriding the default.
+    delay: Mutex<Option<Duration>>,
+    /// The time the next request is allowed to start.
+    next: Mutex<Option<Instant>>,
+    /// The amount of requests waiting.
+    queued: AtomicUsize,
+    /// The adaptive delay state.
+    backoff: Mutex<Backoff>,
+    /// The robots `Request-rate` limit.
+    rate: Mutex<Option<TokenBucket>>,
+}
+
+/// Schedule requests per host with a concurrency limit and a delay between requests for each host.
+#[derive(Debug)]
+pub struct HostScheduler {
+    /// The max amount of requests to run at once for a host.
+    concurrency: usize,
+    /// The default delay between requests to a host.
+    delay: Duration,
+    /// The queues for each host.
+    hosts: Mutex<HashMap<String, Arc<HostQueue>>>,
+    /// Adapt the delay for a host to the responses.
+    adaptive: Option<AdaptiveThrottle>,
+}
+
+impl HostScheduler {
+    /// A new scheduler with the max concurrent requests and the default delay between requests to a host.
+    pub fn new(concurrency: usize, delay: Duration, adaptive: Option<AdaptiveThrottle>) -> Self {
+        Self {
+            concurrency: concurrency.clamp(1, Semaphore::MAX_PERMITS),
+            delay,
+            hosts: Mutex::new(HashMap::new()),
+            adaptive,
+        }
+    }
+
+    /// Get the queue for the host creating it if needed.
+    fn host(&self, host: &str) -> Arc<HostQueue> {
+        let mut hosts = self.hosts.lock().unwrap();
+
+        match hosts.get(host) {
+            Some(queue) => queue.clone(),
+            _ => {
+                let queue = Arc::new(HostQueue {
+                    semaphore: Arc::new(Semaphore::new(self.concurrency)),
+                    delay: Mutex::new(None),
+                    next: Mutex::new(None),
+                    queued: AtomicUsize::new(0),
+                    backoff: Mutex::new(Backoff::default()),
+                    rate: Mutex::new(None),
+                });
+                hosts.insert(host.to_string(), queue.clone());
+                queue
+            }
+        }
+    }
+
+    /// Set the delay between requests for the host. This is used for the robots `Crawl-delay`.
+    pub fn set_delay(&self, host: &str, delay: Duration) {
+        *self.host(host).delay.lock().unwrap() = Some(delay);
+    }
+
+    /// Limit the host to the amount of requests in the period. This is used for the robots `Request-rate`.
+    pub fn set_request_rate(&self, host: &str, requests: usize, period: Duration) {
+        *self.host(host).rate.lock().unwrap() = Some(TokenBucket::new(requests, period));
+    }
+
+    /// Remove the delay and the request rate set for the host going back to the default delay.
+    pub fn reset_throttle(&self, host: &str) {
+        let queue = self.host(host);
+
+        *queue.delay.lock().unwrap() = None;
+        *queue.rate.lock().unwrap() = None;
+    }
+
+    /// The delay between requests for the host.
+    pub fn host_delay(&self, host: &str) -> Duration {
+        match *self.host(host).delay.lock().unwrap() {
+            Some(delay) => delay,
+            _ => self.delay,
+        }
+    }
+
+    /// Add a request for the url to the host queue. Await `HostTicket::ready` before sending the request.
+    pub fn queue(self: Arc<Self>, url: &str) -> HostTicket {
+        let host = host_key(url);
+        let queue = self.host(&host);
+        let delay = self.host_delay(&host);
+
+        queue.queued.fetch_add(1, Ordering::Relaxed);
+
+        HostTicket {
+            queue,
+            delay,
+            queued: true,
+        }
+    }
+
+    /// Adapt the delay for the host of the url to the response. Rate limited hosts and hosts with a rising latency are slowed down and healthy hosts are sped back up.
+    /// The latency is the time the request took from the page. Returns true if the host is backing off.
+    pub fn record(
+        &self,
+        url: &str,
+        status_code: StatusCode,
+        retry_after: Option<Duration>,
+        latency: Option<Duration>,
+    ) -> bool {
+        match self.adaptive {
+            Some(ref adaptive) => {
+                let queue = self.host(&host_key(url));
+                let mut backoff = queue.backoff.lock().unwrap();
+                let slow = match latency {
+                    Some(latency) if adaptive.latency_factor > 0.0 => {
+                        backoff.record_latency(latency, adaptive.latency_factor)
+                    }
+                    _ => false,
+                };
+
+                if is_rate_limited(status_code) {
+                    backoff.delay = adaptive.slow_down(backoff.delay);
+
+                    match retry_after {
+                        Some(retry_after) => {
+                            backoff.until =
+                                Some(Instant::now() + retry_after.min(adaptive.max_delay));
+                        }
+                        _ => (),
+                    }
+                } else if slow {
+                    backoff.delay = adaptive.slow_down(backoff.delay);
+                } else if status_code.is_success() {
+                    backoff.delay = adaptive.speed_up(backoff.delay);
+                }
+
+                backoff.active()
+            }
+            _ => false,
+        }
+    }
+
+    /// A host is being slowed down.
+    pub fn is_backing_off(&self) -> bool {
+        self.hosts
+            .lock()
+            .unwrap()
+            .values()
+            .any(|queue| queue.backoff.lock().unwrap().active())
+    }
+
+    /// The amount of requests waiting for each host.
+    pub fn queue_depth(&self) -> HashMap<String, usize> {
+        self.hosts
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(host, queue)| (host.clone(), queue.queued.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+/// A request waiting in a host queue.
+#[derive(Debug)]
+pub struct HostTicket {
+    /// The queue of the host.
+    queue: Arc<HostQueue>,
+    /// The delay between requests to the host.
+    delay: Duration,
+    /// The request is still counted as waiting.
+    queued: bool,
+}
+
+impl HostTicket {
+    /// Wait until the host allows the request. The returned permit holds the host concurrency slot until dropped.
+    pub async fn ready(mut self) -> HostPermit {
+        let permit = self.queue.semaphore.clone().acquire_owned().await.ok();
+
+        let (delay, until) = {
+            let backoff = self.queue.backoff.lock().unwrap();
+            (self.delay.max(backoff.delay), backoff.until)
+        };
+
+        let start = {
+            let mut next = self.queue.next.lock().unwrap();
+            let now = Instant::now();
+            let start = match *next {
+                Some(next) if next > now => next,
+                _ => now,
+            };
+            let start = match until {
+                Some(until) if until > start => until,
+                _ => start,
+            };
+            let start = match *self.queue.rate.lock().unwrap() {
+                Some(ref mut rate) => rate.reserve(start),
+                _ => start,
+            };
+            *next = Some(start + delay);
+            start
+        };
+
+        tokio::time::sleep_until(start).await;
+
+        self.queue.queued.fetch_sub(1, Ordering::Relaxed);
+        self.queued = false;
+
+        HostPermit { _permit: permit }
+    }
+}
+
+impl Drop for HostTicket {
+    fn drop(&mut self) {
+        if self.queued {
+            self.queue.queued.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The permit for a request to a host holding the host concurrency slot until dropped.
+#[derive(Debug)]
+pub struct HostPermit {
+    /// The host concurrency slot.
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// The host of the url used to group requests.
+pub fn host_key(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(u) => u.host_str().unwrap_or_default().to_ascii_lowercase(),
+        _ => Default::default(),
+    }
+}
+
+/// Order the links taking one link from each host in turn. The order of the links for a host is kept.
+pub fn round_robin(links: Vec<CaseInsensitiveString>) -> Vec<CaseInsensitiveString> {
+    let total = links.len();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut hosts: Vec<VecDeque<CaseInsensitiveString>> = Vec::new();
+
+    for link in links {
+        let host = host_key(link.inner());
+
+        match index.get(&host) {
+            Some(i) => hosts[*i].push_back(link),
+            _ => {
+                index.insert(host, hosts.len());
+                hosts.push(VecDeque::from([link]));
+            }
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(total);
+
+    while ordered.len() < total {
+        for queue in hosts.iter_mut() {
+            match queue.pop_front() {
+                Some(link) => ordered.push(link),
+                _ => (),
+            }
+        }
+    }
+
+    ordered
+}
+
+#[test]
+fn host_round_robin() {
+    let links = round_robin(vec![
+        "https://a.choosealicense.com/1".into(),
+        "https://a.choosealicense.com/2".into(),
+        "https://b.choosealicense.com/1".into(),
+        "https://a.choosealicense.com/3".into(),
+        "https://b.choosealicense.com/2".into(),
+    ]);
+
+    assert_eq!(
+        links,
+        vec![
+            "https://a.choosealicense.com/1".into(),
+            "https://b.choosealicense.com/1".into(),
+            "https://a.choosealicense.com/2".into(),
+            "https://b.choosealicense.com/2".into(),
+            "https://a.choosealicense.com/3".into(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn host_scheduler_delay() {
+    let scheduler = Arc::new(HostScheduler::new(1, Duration::from_millis(50), None));
+    scheduler.set_delay("b.choosealicense.com", Duration::ZERO);
+
+    let first = scheduler.clone().queue("https://a.choosealicense.com/1");
+    let second = scheduler.clone().queue("https://a.choosealicense.com/2");
+    let other = scheduler.clone().queue("https://b.choosealicense.com/1");
+
+    assert_eq!(
+        scheduler.queue_depth().get("a.choosealicense.com"),
+        Some(&2)
+    );
+
+    let start = Instant::now();
+    drop(first.ready().await);
+    drop(other.ready().await);
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    drop(second.ready().await);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(
+        scheduler.queue_depth().get("a.choosealicense.com"),
+        Some(&0)
+    );
+}
+
+#[test]
+fn host_request_rate() {
+    let mut rate = TokenBucket::new(2, Duration::from_secs(10));
+    let start = Instant::now();
+
+    assert_eq!(rate.reserve(start), start);
+    assert_eq!(rate.reserve(start), start);
+    assert_eq!(rate.reserve(start), start + Duration::from_secs(5));
+    assert_eq!(rate.reserve(start), start + Duration::from_secs(10));
+
+    let later = start + Duration::from_secs(30);
+
+    assert_eq!(rate.reserve(later), later);
+    assert_eq!(rate.reserve(later), later);
+    assert_eq!(rate.reserve(later), later + Duration::from_secs(5));
+}
+
+#[test]
+fn host_adaptive_throttle() {
+    let scheduler = HostScheduler::new(
+        1,
+        Duration::ZERO,
+        Some(AdaptiveThrottle {
+            latency_factor: 0.0,
+            ..Default::default()
+        }),
+    );
+    let url = "https://choosealicense.com/licenses/";
+
+    assert!(scheduler.record(url, StatusCode::TOO_MANY_REQUESTS, None, None));
+    assert!(scheduler.is_backing_off());
+
+    let mut backing_off = true;
+
+    for _ in 0..16 {
+        backing_off = scheduler.record(url, StatusCode::OK, None, None);
+    }
+
+    assert!(!backing_off);
+    assert!(!scheduler.is_backing_off());
+
+    let scheduler = HostScheduler::new(1, Duration::ZERO, Some(AdaptiveThrottle::default()));
+    let latency = Duration::from_millis(100);
+
+    assert!(!scheduler.record(url, StatusCode::OK, None, Some(latency)));
+    assert!(!scheduler.record(url, StatusCode::OK, None, Some(latency)));
+    assert!(scheduler.record(url, StatusCode::OK, None, Some(latency * 4)));
+}
@@ -0,0 +1,142 @@
+use reqwest::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::StatusCode;
+
+/// The filter that rejected a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseRejection {
+    /// The status code is dropped.
+    StatusCode,
+    /// The MIME type is not allowed.
+    ContentType,
+    /// The body is larger than the max content length.
+    ContentLength,
+}
+
+/// Filter the responses on the status code and headers before the body is downloaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResponseFilter {
+    /// The MIME types allowed like `text/html` or `text/*`. All the types are allowed when empty. Responses without a Content-Type are allowed.
+    pub allowed_mime_types: Vec<String>,
+    /// The max bytes of the body. The Content-Length header is checked first and the download stops once the body is larger.
+    pub max_content_length: Option<u64>,
+    /// The status codes of the responses to drop.
+    pub drop_status_codes: Vec<u16>,
+}
+
+impl ResponseFilter {
+    /// A new filter allowing all the responses.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Allow only the MIME types like `text/html` or `text/*`.
+    pub fn with_allowed_mime_types(mut self, allowed_mime_types: Vec<String>) -> Self {
+        self.allowed_mime_types = allowed_mime_types;
+        self
+    }
+
+    /// Drop the responses with a body larger than the bytes.
+    pub fn with_max_content_length(mut self, max_content_length: Option<u64>) -> Self {
+        self.max_content_length = max_content_length;
+        self
+    }
+
+    /// Drop the responses with the status codes.
+    pub fn with_drop_status_codes(mut self, drop_status_codes: Vec<u16>) -> Self {
+        self.drop_status_codes = drop_status_codes;
+        self
+    }
+
+    /// The MIME type is allowed.
+    pub fn is_mime_type_allowed(&self, content_type: &str) -> bool {
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        self.allowed_mime_types.is_empty()
+            || self.allowed_mime_types.iter().any(|allowed| {
+                let allowed = allowed.trim().to_ascii_lowercase();
+
+                match allowed.strip_suffix("/*") {
+                    Some(kind) => essence.split('/').next() == Some(kind),
+                    _ => allowed == essence,
+                }
+            })
+    }
+
+    /// The body is larger than the max content length.
+    pub fn is_over_length(&self, length: u64) -> bool {
+        match self.max_content_length {
+            Some(max) => length > max,
+            _ => false,
+        }
+    }
+
+    /// Check the status code and headers of the response returning the filter that rejected it.
+    pub fn check(&self, status_code: StatusCode, headers: &HeaderMap) -> Option<ResponseRejection> {
+        let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+        let mime_type_allowed = match header(CONTENT_TYPE) {
+            Some(content_type) => self.is_mime_type_allowed(content_type),
+            _ => true,
+        };
+        let over_length = match header(CONTENT_LENGTH).and_then(|l| l.trim().parse::<u64>().ok()) {
+            Some(length) => self.is_over_length(length),
+            _ => false,
+        };
+
+        if self.drop_status_codes.contains(&status_code.as_u16()) {
+            Some(ResponseRejection::StatusCode)
+        } else if !mime_type_allowed {
+            Some(ResponseRejection::ContentType)
+        } else if over_length {
+            Some(ResponseRejection::ContentLength)
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn response_filter_check() {
+    let filter = ResponseFilter::new()
+        .with_allowed_mime_types(vec!["text/html".into(), "application/*".into()])
+        .with_max_content_length(Some(1024))
+        .with_drop_status_codes(vec![203]);
+    let headers = |content_type: &str, length: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        headers.insert(CONTENT_LENGTH, length.parse().unwrap());
+        headers
+    };
+
+    assert_eq!(
+        filter.check(StatusCode::OK, &headers("text/html; charset=utf-8", "512")),
+        None
+    );
+    assert_eq!(
+        filter.check(StatusCode::OK, &headers("application/pdf", "100")),
+        None
+    );
+    assert_eq!(
+        filter.check(StatusCode::OK, &headers("video/mp4", "100")),
+        Some(ResponseRejection::ContentType)
+    );
+    assert_eq!(
+        filter.check(StatusCode::OK, &headers("TEXT/HTML", "52428800")),
+        Some(ResponseRejection::ContentLength)
+    );
+    assert_eq!(
+        filter.check(
+            StatusCode::NON_AUTHORITATIVE_INFORMATION,
+            &headers("text/html", "1")
+        ),
+        Some(ResponseRejection::StatusCode)
+    );
+    assert_eq!(filter.check(StatusCode::OK, &HeaderMap::new()), None);
+    assert!(filter.is_over_length(1025));
+}
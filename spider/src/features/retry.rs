@@ -0,0 +1,270 @@
+use crate::page::Page;
+use crate::utils::PageResponse;
+use reqwest::StatusCode;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// The kind of network error a request failed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RetryErrorKind {
+    /// The request timed out.
+    Timeout,
+    /// The connection could not be made.
+    Connect,
+    /// The request could not be sent.
+    Request,
+    /// The response body could not be read.
+    Body,
+    /// Any other error.
+    Other,
+}
+
+impl RetryErrorKind {
+    /// The kind of the request error.
+    pub fn from_error(error: &reqwest::Error) -> Self {
+        if error.is_timeout() {
+            RetryErrorKind::Timeout
+        } else if error.is_connect() {
+            RetryErrorKind::Connect
+        } else if error.is_body() || error.is_decode() {
+            RetryErrorKind::Body
+        } else if error.is_request() {
+            RetryErrorKind::Request
+        } else {
+            RetryErrorKind::Other
+        }
+    }
+}
+
+/// Retry requests that failed with a transient error waiting longer between every attempt.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryPolicy {
+    /// The max attempts for a request including the first.
+    pub max_attempts: u32,
+    /// The delay before the first retry. The delay doubles for every retry after.
+    pub backoff_base: Duration,
+    /// The max delay between attempts.
+    pub backoff_cap: Duration,
+    /// Wait a random time up to the delay to spread out the retries.
+    pub jitter: bool,
+    /// The status codes to retry.
+    pub status_codes: Vec<u16>,
+    /// The network errors to retry.
+    pub error_kinds: Vec<RetryErrorKind>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff_base: Duration::from_millis(500),
+            backoff_cap: Duration::from_secs(10),
+            jitter: true,
+            status_codes: vec![408, 500, 502, 504],
+            error_kinds: vec![
+                RetryErrorKind::Timeout,
+                RetryErrorKind::Connect,
+                RetryErrorKind::Body,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A new retry policy with the max attempts for a request.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Default::default()
+        }
+    }
+
+    /// The delay to wait before the attempt after the one made.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .backoff_base
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(31))
+            .min(self.backoff_cap);
+
+        if self.jitter {
+            let random = std::collections::hash_map::RandomState::new()
+                .build_hasher()
+                .finish();
+            delay.mul_f64((random % 1000) as f64 / 1000.0)
+        } else {
+            delay
+        }
+    }
+
+    /// The response failed with a status code or error that is retried.
+    pub fn is_retryable(
+        &self,
+        status_code: StatusCode,
+        error_kind: Option<RetryErrorKind>,
+    ) -> bool {
+        match error_kind {
+            Some(kind) => self.error_kinds.contains(&kind),
+            _ => self.status_codes.contains(&status_code.as_u16()),
+        }
+    }
+}
+
+/// A fetch result that can be retried.
+pub trait Retryable {
+    /// The status code of the response.
+    fn status_code(&self) -> StatusCode;
+    /// The network error the request failed with.
+    fn error_kind(&self) -> Option<RetryErrorKind>;
+    /// Record the attempts made.
+    fn set_attempts(&mut self, attempts: u32);
+    /// Record the time the last attempt took.
+    fn set_latency(&mut self, latency: Duration);
+}
+
+impl Retryable for Page {
+    fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+    fn error_kind(&self) -> Option<RetryErrorKind> {
+        self.error_kind
+    }
+    fn set_attempts(&mut self, attempts: u32) {
+        self.attempts = attempts
+    }
+    fn set_latency(&mut self, latency: Duration) {
+        self.latency = Some(latency)
+    }
+}
+
+impl Retryable for PageResponse {
+    fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+    fn error_kind(&self) -> Option<RetryErrorKind> {
+        self.error_kind
+    }
+    fn set_attempts(&mut self, attempts: u32) {
+        self.attempts = attempts
+    }
+    fn set_latency(&mut self, latency: Duration) {
+        self.latency = Some(latency)
+    }
+}
+
+#[cfg(feature = "chrome")]
+impl Retryable for Result<PageResponse, chromiumoxide::error::CdpError> {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Ok(res) => res.status_code,
+            _ => Default::default(),
+        }
+    }
+    fn error_kind(&self) -> Option<RetryErrorKind> {
+        match self {
+            Ok(res) => res.error_kind,
+            Err(chromiumoxide::error::CdpError::Timeout) => Some(RetryErrorKind::Timeout),
+            _ => Some(RetryErrorKind::Other),
+        }
+    }
+    fn set_attempts(&mut self, attempts: u32) {
+        match self {
+            Ok(res) => res.attempts = attempts,
+            _ => (),
+        }
+    }
+    fn set_latency(&mut self, latency: Duration) {
+        match self {
+            Ok(res) => res.latency = Some(latency),
+            _ => (),
+        }
+    }
+}
+
+/// Run the fetch retrying transient failures with the policy. The attempts made are recorded on the result and the error of the last attempt is kept.
+pub async fn retry_fetch<T, F, Fut>(policy: &Option<RetryPolicy>, mut fetch: F) -> T
+where
+    T: Retryable,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let started = std::time::Instant::now();
+        let mut res = fetch().await;
+
+        match policy {
+            Some(policy)
+                if attempt < policy.max_attempts
+                    && policy.is_retryable(res.status_code(), res.error_kind()) =>
+            {
+                tokio::time::sleep(policy.backoff(attempt)).await;
+                attempt += 1;
+            }
+            _ => {
+                res.set_attempts(attempt);
+                res.set_latency(started.elapsed());
+                return res;
+            }
+        }
+    }
+}
+
+#[test]
+fn retry_backoff() {
+    let policy = RetryPolicy {
+        jitter: false,
+        backoff_base: Duration::from_millis(100),
+        backoff_cap: Duration::from_millis(350),
+        ..Default::default()
+    };
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(350));
+    assert!(
+        RetryPolicy::default().backoff(2) <= Duration::from_secs(1),
+        "jitter never waits past the backoff"
+    );
+}
+
+#[tokio::test]
+async fn retry_fetch_attempts() {
+    let policy = Some(RetryPolicy {
+        backoff_base: Duration::from_millis(1),
+        ..Default::default()
+    });
+    let mut calls = 0;
+
+    let res = retry_fetch(&policy, || {
+        calls += 1;
+        let status_code = if calls < 2 {
+            StatusCode::BAD_GATEWAY
+        } else {
+            StatusCode::OK
+        };
+        async move {
+            PageResponse {
+                status_code,
+                ..Default::default()
+            }
+        }
+    })
+    .await;
+
+    assert_eq!(res.attempts, 2);
+    assert_eq!(res.status_code, StatusCode::OK);
+
+    let res = retry_fetch(&policy, || async {
+        PageResponse {
+            status_code: StatusCode::INTERNAL_SERVER_ERROR,
+            ..Default::default()
+        }
+    })
+    .await;
+
+    assert_eq!(res.attempts, 3);
+}
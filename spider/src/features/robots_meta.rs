@@ -0,0 +1,170 @@
+use crate::packages::robotparser::parser::product_token;
+use crate::packages::scraper::{Html, Selector};
+use reqwest::header::HeaderMap;
+
+/// The header with the robots directives of a response.
+pub const X_ROBOTS_TAG: &str = "x-robots-tag";
+
+/// The directives with a value like `max-snippet: 20` that are not user agent prefixes in the X-Robots-Tag header.
+const VALUE_DIRECTIVES: [&str; 4] = [
+    "unavailable_after",
+    "max-snippet",
+    "max-image-preview",
+    "max-video-preview",
+];
+
+lazy_static! {
+    /// The meta tags with a name and content.
+    static ref META_SELECTOR: Selector = Selector::parse("meta[name][content]").unwrap();
+}
+
+/// The robots directives of a page from the meta robots tags and the X-Robots-Tag headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RobotsDirectives {
+    /// The page asked not to be indexed.
+    pub noindex: bool,
+    /// The page asked not to follow its links.
+    pub nofollow: bool,
+}
+
+impl RobotsDirectives {
+    /// Add the comma separated directives like `noindex, nofollow`. The `none` directive is the same as `noindex, nofollow`.
+    pub fn add(&mut self, content: &str) {
+        for directive in content.split(',') {
+            match directive.trim().to_ascii_lowercase().as_str() {
+                "noindex" => self.noindex = true,
+                "nofollow" => self.nofollow = true,
+                "none" => {
+                    self.noindex = true;
+                    self.nofollow = true;
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Read the robots directives of the pages for a user agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RobotsMeta {
+    /// The lowercase product token of the user agent like `foobot` for `FooBot/2.1`.
+    product_token: String,
+}
+
+impl RobotsMeta {
+    /// Read the directives for the user agent. The meta tags named `robots` or the product token of the user agent apply.
+    pub fn new(user_agent: Option<&str>) -> Self {
+        Self {
+            product_token: product_token(user_agent.unwrap_or_default()).to_ascii_lowercase(),
+        }
+    }
+
+    /// The name is the product token of the user agent.
+    fn is_user_agent(&self, name: &str) -> bool {
+        !self.product_token.is_empty() && name.trim().eq_ignore_ascii_case(&self.product_token)
+    }
+
+    /// Add the X-Robots-Tag header value. The values prefixed with a user agent like `foobot: noindex` only apply to the user agent.
+    fn add_header(&self, directives: &mut RobotsDirectives, value: &str) {
+        match value.split_once(':') {
+            Some((name, content))
+                if !name.contains(',')
+                    && !VALUE_DIRECTIVES
+                        .iter()
+                        .any(|d| name.trim().eq_ignore_ascii_case(d)) =>
+            {
+                if self.is_user_agent(name) {
+                    directives.add(content)
+                }
+            }
+            _ => directives.add(value),
+        }
+    }
+
+    /// The directives of the page from the meta robots tags of the html and the X-Robots-Tag header values.
+    pub fn directives(&self, html: &str, x_robots_tag: &[String]) -> RobotsDirectives {
+        let mut directives = RobotsDirectives::default();
+
+        for value in x_robots_tag {
+            self.add_header(&mut directives, value);
+        }
+
+        if !html.is_empty() && !html.starts_with("<?xml") {
+            let html = Html::parse_document(html);
+
+            for element in html.select(&META_SELECTOR) {
+                match (element.attr("name"), element.attr("content")) {
+                    (Some(name), Some(content))
+                        if name.trim().eq_ignore_ascii_case("robots")
+                            || self.is_user_agent(name) =>
+                    {
+                        directives.add(content)
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        directives
+    }
+}
+
+/// The X-Robots-Tag header values of the response.
+pub fn x_robots_tag(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(X_ROBOTS_TAG)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .map(String::from)
+        .collect()
+}
+
+/// The rel attribute of an anchor has `nofollow`.
+pub fn is_nofollow(rel: Option<&str>) -> bool {
+    rel.is_some_and(|rel| {
+        rel.split_whitespace()
+            .any(|rel| rel.eq_ignore_ascii_case("nofollow"))
+    })
+}
+
+#[test]
+fn robots_meta_directives() {
+    let robots_meta = RobotsMeta::new(Some("FooBot/2.1"));
+    let html = r#"<html><head><meta name="robots" content="noindex"><meta name="otherbot" content="nofollow"></head></html>"#;
+
+    assert_eq!(
+        robots_meta.directives(html, &[]),
+        RobotsDirectives {
+            noindex: true,
+            nofollow: false
+        }
+    );
+    assert_eq!(
+        robots_meta.directives(r#"<meta name="FOOBOT" content="none">"#, &[]),
+        RobotsDirectives {
+            noindex: true,
+            nofollow: true
+        }
+    );
+    assert_eq!(
+        robots_meta.directives(
+            "",
+            &[
+                "otherbot: noindex".into(),
+                "max-snippet: 20, nofollow".into()
+            ]
+        ),
+        RobotsDirectives {
+            noindex: false,
+            nofollow: true
+        }
+    );
+    assert!(
+        robots_meta
+            .directives("", &["foobot: noindex, nofollow".into()])
+            .noindex
+    );
+    assert!(is_nofollow(Some("external NoFollow")));
+    assert!(!is_nofollow(Some("noopener")));
+}
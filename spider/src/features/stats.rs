@@ -0,0 +1,342 @@
+use crate::features::politeness::HostScheduler;
+use crate::features::retry::RetryErrorKind;
+use crate::page::Page;
+use crate::CaseInsensitiveString;
+use hashbrown::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The reason a link was skipped and not crawled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SkipReason {
+    /// The link was already crawled.
+    Visited,
+    /// The link is over the crawl depth.
+    Depth,
+    /// The link is a crawler trap.
+    Trap,
+    /// The crawl budget for the link was used up.
+    Budget,
+    /// The link is forbidden by robots.txt.
+    Robots,
+    /// The link is not in the whitelist or is in the blacklist.
+    Filtered,
+    /// The link hook rejected the link.
+    LinkHook,
+    /// The link was found on a near-duplicate page.
+    Duplicate,
+}
+
+/// A snapshot of the statistics of a crawl.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrawlStats {
+    /// The pages fetched.
+    pub pages_fetched: usize,
+    /// The bytes of the page content downloaded.
+    pub bytes_downloaded: u64,
+    /// The amount of pages fetched for each status code.
+    pub status_codes: HashMap<u16, usize>,
+    /// The amount of requests that failed for each kind of network error.
+    pub errors: HashMap<RetryErrorKind, usize>,
+    /// The amount of links skipped for each reason.
+    pub links_skipped: HashMap<SkipReason, usize>,
+    /// The links waiting to be crawled.
+    pub frontier_size: usize,
+    /// The requests waiting for each host with host politeness or adaptive throttling.
+    pub host_queue_depth: HashMap<String, usize>,
+    /// The time since the crawl started until now or the end of the crawl.
+    pub elapsed: Duration,
+    /// The pages fetched per second.
+    pub pages_per_second: f64,
+    /// The average time a request took.
+    pub average_latency: Duration,
+}
+
+/// The response stats of a page fetched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageStats {
+    /// The url of the page.
+    pub url: CaseInsensitiveString,
+    /// The status code of the page or `0` when no response was received.
+    pub status_code: u16,
+    /// The bytes of the page content.
+    pub bytes: usize,
+    /// The kind of network error the request failed with.
+    pub error_kind: Option<RetryErrorKind>,
+    /// The error of the request or the error status code.
+    pub error: Option<String>,
+    /// The final url if the request was redirected.
+    pub redirect: Option<String>,
+    /// The time the request took.
+    pub latency: Option<Duration>,
+}
+
+impl PageStats {
+    /// The response stats of the page.
+    pub fn from_page(page: &Page) -> Self {
+        let status_code = page.status_code;
+
+        Self {
+            url: page.get_url().into(),
+            status_code: if page.error_kind.is_some() {
+                0
+            } else {
+                status_code.as_u16()
+            },
+            bytes: page.get_html_bytes_u8().len(),
+            error_kind: page.error_kind,
+            error: if page.error_kind.is_some()
+                || status_code.is_client_error()
+                || status_code.is_server_error()
+            {
+                Some(match &page.error_status {
+                    Some(error) => error.clone(),
+                    _ => status_code.to_string(),
+                })
+            } else {
+                None
+            },
+            redirect: match &page.final_redirect_destination {
+                Some(destination) if destination != page.get_url() => Some(destination.clone()),
+                _ => None,
+            },
+            latency: page.latency,
+        }
+    }
+}
+
+/// The stats with the totals to compute the averages.
+#[derive(Debug, Default)]
+struct StatsState {
+    /// The stats counted.
+    stats: CrawlStats,
+    /// The total time of the requests with a latency.
+    latency_total: Duration,
+    /// The amount of requests with a latency.
+    latency_count: u32,
+    /// The time the crawl started.
+    started: Option<Instant>,
+    /// The time the crawl ended.
+    finished: Option<Instant>,
+    /// The per host scheduler to read the host queues from.
+    host_scheduler: Option<Arc<HostScheduler>>,
+}
+
+impl StatsState {
+    /// The time since the crawl started until now or the end of the crawl.
+    fn elapsed(&self) -> Duration {
+        match (self.started, self.finished) {
+            (Some(started), Some(finished)) => finished.duration_since(started),
+            (Some(started), _) => started.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// A handle to the live statistics of a crawl. The handle can be cloned and read from another task while the crawl runs.
+#[derive(Debug, Clone, Default)]
+pub struct CrawlStatsHandle {
+    /// The shared state.
+    state: Arc<Mutex<StatsState>>,
+}
+
+impl CrawlStatsHandle {
+    /// A new empty handle.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// A snapshot of the stats.
+    pub fn snapshot(&self) -> CrawlStats {
+        match self.state.lock() {
+            Ok(state) => {
+                let mut stats = state.stats.clone();
+
+                stats.elapsed = state.elapsed();
+                stats.pages_per_second = if stats.elapsed.is_zero() {
+                    0.0
+                } else {
+                    stats.pages_fetched as f64 / stats.elapsed.as_secs_f64()
+                };
+                stats.average_latency = if state.latency_count > 0 {
+                    state.latency_total / state.latency_count
+                } else {
+                    Duration::ZERO
+                };
+                stats.host_queue_depth = match state.host_scheduler {
+                    Some(ref scheduler) => scheduler.queue_depth(),
+                    _ => Default::default(),
+                };
+
+                stats
+            }
+            _ => Default::default(),
+        }
+    }
+
+    /// The time since the crawl started until now or the end of the crawl.
+    pub fn elapsed(&self) -> Duration {
+        match self.state.lock() {
+            Ok(state) => state.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// The bytes of the page content downloaded.
+    pub fn bytes_downloaded(&self) -> u64 {
+        match self.state.lock() {
+            Ok(state) => state.stats.bytes_downloaded,
+            _ => 0,
+        }
+    }
+
+    /// Start the crawl clock if the crawl is not already running.
+    pub fn start(&self) {
+        match self.state.lock() {
+            Ok(mut state) if state.started.is_none() || state.finished.is_some() => {
+                state.started = Some(Instant::now());
+                state.finished = None;
+            }
+            _ => (),
+        }
+    }
+
+    /// Stop the crawl clock.
+    pub fn finish(&self) {
+        match self.state.lock() {
+            Ok(mut state) if state.started.is_some() && state.finished.is_none() => {
+                state.finished = Some(Instant::now());
+            }
+            _ => (),
+        }
+    }
+
+    /// Count the page fetched.
+    pub fn record_page(&self, page: &PageStats) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.stats.pages_fetched += 1;
+                state.stats.bytes_downloaded += page.bytes as u64;
+                *state
+                    .stats
+                    .status_codes
+                    .entry(page.status_code)
+                    .or_insert(0) += 1;
+
+                match page.error_kind {
+                    Some(kind) => *state.stats.errors.entry(kind).or_insert(0) += 1,
+                    _ => (),
+                }
+
+                match page.latency {
+                    Some(latency) => {
+                        state.latency_total += latency;
+                        state.latency_count += 1;
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Count the links skipped for the reason.
+    pub fn record_skipped(&self, reason: SkipReason, amount: usize) {
+        match self.state.lock() {
+            Ok(mut state) if amount > 0 => {
+                *state.stats.links_skipped.entry(reason).or_insert(0) += amount;
+            }
+            _ => (),
+        }
+    }
+
+    /// Set the links waiting to be crawled.
+    pub fn set_frontier_size(&self, size: usize) {
+        match self.state.lock() {
+            Ok(mut state) => state.stats.frontier_size = size,
+            _ => (),
+        }
+    }
+
+    /// Set the per host scheduler to read the host queues from.
+    pub fn set_host_scheduler(&self, host_scheduler: Option<Arc<HostScheduler>>) {
+        match self.state.lock() {
+            Ok(mut state) => state.host_scheduler = host_scheduler,
+            _ => (),
+        }
+    }
+
+    /// Reset the stats. The per host scheduler is kept.
+    pub fn clear(&self) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                let host_scheduler = state.host_scheduler.take();
+                *state = Default::default();
+                state.host_scheduler = host_scheduler;
+            }
+            _ => (),
+        }
+    }
+}
+
+#[test]
+fn crawl_stats_snapshot() {
+    let handle = CrawlStatsHandle::new();
+    let reader = handle.clone();
+
+    handle.start();
+    handle.record_page(&PageStats {
+        status_code: 200,
+        bytes: 1024,
+        latency: Some(Duration::from_millis(100)),
+        ..Default::default()
+    });
+    handle.record_page(&PageStats {
+        status_code: 200,
+        bytes: 512,
+        latency: Some(Duration::from_millis(300)),
+        ..Default::default()
+    });
+    handle.record_page(&PageStats {
+        error_kind: Some(RetryErrorKind::Timeout),
+        ..Default::default()
+    });
+    handle.record_skipped(SkipReason::Robots, 2);
+    handle.record_skipped(SkipReason::Visited, 0);
+    handle.set_frontier_size(7);
+    handle.finish();
+
+    let stats = reader.snapshot();
+
+    assert_eq!(stats.pages_fetched, 3);
+    assert_eq!(stats.bytes_downloaded, 1536);
+    assert_eq!(stats.status_codes.get(&200), Some(&2));
+    assert_eq!(stats.status_codes.get(&0), Some(&1));
+    assert_eq!(stats.errors.get(&RetryErrorKind::Timeout), Some(&1));
+    assert_eq!(stats.links_skipped.get(&SkipReason::Robots), Some(&2));
+    assert_eq!(stats.links_skipped.get(&SkipReason::Visited), None);
+    assert_eq!(stats.frontier_size, 7);
+    assert_eq!(stats.average_latency, Duration::from_millis(200));
+    assert_eq!(stats.elapsed, reader.snapshot().elapsed);
+    assert_eq!(stats.elapsed, reader.elapsed());
+    assert_eq!(reader.bytes_downloaded(), 1536);
+
+    handle.clear();
+
+    assert_eq!(reader.snapshot(), CrawlStats::default());
+
+    let scheduler = Arc::new(HostScheduler::new(1, Duration::ZERO, None));
+    let _ticket = scheduler
+        .clone()
+        .queue("https://choosealicense.com/licenses/");
+
+    handle.set_host_scheduler(Some(scheduler));
+    handle.clear();
+
+    assert_eq!(
+        reader.snapshot().host_queue_depth.get("choosealicense.com"),
+        Some(&1)
+    );
+}
@@ -0,0 +1,904 @@
+use crate::features::frontier::{BreadthFirst, Frontier, FrontierLink};
+use crate::CaseInsensitiveString;
+use hashbrown::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The false positive rate of the Bloom filter in front of the links on disk.
+const FALSE_POSITIVE_RATE: f64 = 0.01;
+/// The amount of runs of the same size merged into one run.
+const MERGE_RUNS: usize = 4;
+/// The bytes of a run index entry with the hash, the data offset and the data length.
+const ENTRY_BYTES: u64 = 20;
+
+lazy_static! {
+    /// The files created by the process to keep the file names unique.
+    static ref FILE_ID: AtomicUsize = AtomicUsize::new(0);
+}
+
+/// Keep the visited links and the frontier on disk once they are over the memory limit. The links on disk are deduplicated exactly with a Bloom filter in front to skip most disk reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiskStorage {
+    /// The directory to keep the files in. The files are removed once the crawl state is cleared.
+    pub path: PathBuf,
+    /// The max amount of links kept in memory for the visited links and the frontier each.
+    pub memory_links: usize,
+    /// The expected amount of links used to size the Bloom filter.
+    pub expected_links: usize,
+}
+
+impl Default for DiskStorage {
+    fn default() -> Self {
+        Self {
+            path: std::env::temp_dir().join("spider"),
+            memory_links: 100_000,
+            expected_links: 10_000_000,
+        }
+    }
+}
+
+impl DiskStorage {
+    /// Keep the links in the directory.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A unique file path in the directory.
+fn unique_path(dir: &Path, name: &str, extension: &str) -> PathBuf {
+    dir.join(string_concat!(
+        name,
+        "-",
+        std::process::id().to_string(),
+        "-",
+        FILE_ID.fetch_add(1, Ordering::Relaxed).to_string(),
+        ".",
+        extension
+    ))
+}
+
+/// The hashes of the link ignoring ascii case.
+fn link_hashes(link: &str) -> (u64, u64) {
+    let link = link.to_ascii_lowercase();
+    let mut first = std::collections::hash_map::DefaultHasher::new();
+    let mut second = std::collections::hash_map::DefaultHasher::new();
+
+    link.hash(&mut first);
+    0xa5a5_a5a5_u64.hash(&mut second);
+    link.hash(&mut second);
+
+    (first.finish(), second.finish())
+}
+
+/// A Bloom filter of the link hashes.
+#[derive(Debug, Clone)]
+struct BloomFilter {
+    /// The bits set.
+    bits: Vec<u64>,
+    /// The amount of bits set for each link.
+    hashes: u64,
+}
+
+impl BloomFilter {
+    /// A Bloom filter sized for the expected amount of links.
+    fn new(expected_links: usize) -> Self {
+        let links = expected_links.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-(links * FALSE_POSITIVE_RATE.ln()) / (ln2 * ln2))
+            .ceil()
+            .max(64.0);
+
+        Self {
+            bits: vec![0; (bits as usize).div_ceil(64)],
+            hashes: ((bits / links) * ln2).round().max(1.0) as u64,
+        }
+    }
+
+    /// The bit positions of the hashes.
+    fn positions(&self, (first, second): (u64, u64)) -> impl Iterator<Item = usize> {
+        let size = self.bits.len() as u64 * 64;
+
+        (0..self.hashes).map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % size) as usize)
+    }
+
+    /// Add the hashes.
+    fn insert(&mut self, hashes: (u64, u64)) {
+        for position in self.positions(hashes).collect::<Vec<_>>() {
+            self.bits[position / 64] |= 1 << (position % 64);
+        }
+    }
+
+    /// The hashes might have been added.
+    fn contains(&self, hashes: (u64, u64)) -> bool {
+        self.positions(hashes)
+            .all(|position| self.bits[position / 64] & (1 << (position % 64)) != 0)
+    }
+
+    /// Remove all the hashes.
+    fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|bits| *bits = 0);
+    }
+}
+
+/// A sorted file of links with an index of the link hashes to look the links up with a binary search.
+#[derive(Debug)]
+struct Run {
+    /// The path of the file.
+    path: PathBuf,
+    /// The file.
+    file: File,
+    /// The amount of links.
+    count: u64,
+    /// The amount of times the links were merged.
+    level: u32,
+}
+
+/// Read the links of a run in order.
+struct RunReader {
+    /// The index entries.
+    index: BufReader<File>,
+    /// The links.
+    data: BufReader<File>,
+    /// The links left.
+    left: u64,
+}
+
+impl RunReader {
+    /// The next link with the hash.
+    fn next(&mut self) -> io::Result<Option<(u64, String)>> {
+        if self.left == 0 {
+            return Ok(None);
+        }
+
+        let mut entry = [0u8; ENTRY_BYTES as usize];
+        self.index.read_exact(&mut entry)?;
+
+        let (hash, _, len) = parse_entry(&entry);
+        let mut link = vec![0u8; len as usize];
+        self.data.read_exact(&mut link)?;
+        self.left -= 1;
+
+        Ok(Some((hash, String::from_utf8_lossy(&link).into_owned())))
+    }
+}
+
+/// The hash, data offset and data length of an index entry.
+fn parse_entry(entry: &[u8; ENTRY_BYTES as usize]) -> (u64, u64, u32) {
+    let mut hash = [0u8; 8];
+    let mut offset = [0u8; 8];
+    let mut len = [0u8; 4];
+
+    hash.copy_from_slice(&entry[0..8]);
+    offset.copy_from_slice(&entry[8..16]);
+    len.copy_from_slice(&entry[16..20]);
+
+    (
+        u64::from_le_bytes(hash),
+        u64::from_le_bytes(offset),
+        u32::from_le_bytes(len),
+    )
+}
+
+/// Write the sorted links to a run file.
+fn write_run(
+    path: PathBuf,
+    count: u64,
+    level: u32,
+    mut links: impl FnMut() -> io::Result<Option<(u64, String)>>,
+) -> io::Result<Run> {
+    let data_start = 8 + count * ENTRY_BYTES;
+    let mut index = BufWriter::new(File::create(&path)?);
+    let mut data_file = OpenOptions::new().write(true).open(&path)?;
+    data_file.seek(SeekFrom::Start(data_start))?;
+    let mut data = BufWriter::new(data_file);
+    let mut offset: u64 = 0;
+
+    index.write_all(&count.to_le_bytes())?;
+
+    while let Some((hash, link)) = links()? {
+        index.write_all(&hash.to_le_bytes())?;
+        index.write_all(&offset.to_le_bytes())?;
+        index.write_all(&(link.len() as u32).to_le_bytes())?;
+        data.write_all(link.as_bytes())?;
+        offset += link.len() as u64;
+    }
+
+    index.flush()?;
+    data.flush()?;
+
+    Ok(Run {
+        file: File::open(&path)?,
+        path,
+        count,
+        level,
+    })
+}
+
+impl Run {
+    /// The index entry at the position.
+    fn entry(&self, position: u64) -> io::Result<(u64, u64, u32)> {
+        let mut entry = [0u8; ENTRY_BYTES as usize];
+        let mut file = &self.file;
+
+        file.seek(SeekFrom::Start(8 + position * ENTRY_BYTES))?;
+        file.read_exact(&mut entry)?;
+
+        Ok(parse_entry(&entry))
+    }
+
+    /// The link at the data offset.
+    fn link(&self, offset: u64, len: u32) -> io::Result<String> {
+        let mut link = vec![0u8; len as usize];
+        let mut file = &self.file;
+
+        file.seek(SeekFrom::Start(8 + self.count * ENTRY_BYTES + offset))?;
+        file.read_exact(&mut link)?;
+
+        Ok(String::from_utf8_lossy(&link).into_owned())
+    }
+
+    /// The run has the link ignoring ascii case.
+    fn contains(&self, hash: u64, link: &str) -> io::Result<bool> {
+        let (mut low, mut high) = (0, self.count);
+
+        while low < high {
+            let middle = low + (high - low) / 2;
+
+            if self.entry(middle)?.0 < hash {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        while low < self.count {
+            let (entry_hash, offset, len) = self.entry(low)?;
+
+            if entry_hash != hash {
+                break;
+            }
+            if self.link(offset, len)?.eq_ignore_ascii_case(link) {
+                return Ok(true);
+            }
+
+            low += 1;
+        }
+
+        Ok(false)
+    }
+
+    /// Read the links in order.
+    fn reader(&self) -> io::Result<RunReader> {
+        let mut index = File::open(&self.path)?;
+        let mut data = File::open(&self.path)?;
+
+        index.seek(SeekFrom::Start(8))?;
+        data.seek(SeekFrom::Start(8 + self.count * ENTRY_BYTES))?;
+
+        Ok(RunReader {
+            index: BufReader::new(index),
+            data: BufReader::new(data),
+            left: self.count,
+        })
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The links in memory and on disk.
+#[derive(Debug)]
+struct LinkSetState {
+    /// The directory of the run files.
+    dir: PathBuf,
+    /// The name of the run files.
+    name: String,
+    /// The links not written to disk yet.
+    buffer: HashSet<CaseInsensitiveString>,
+    /// The run files from the oldest to the newest.
+    runs: Vec<Run>,
+    /// The Bloom filter of the links on disk.
+    bloom: BloomFilter,
+    /// The links removed from the run files. The runs are immutable so the links are skipped instead.
+    removed: HashSet<CaseInsensitiveString>,
+    /// The max amount of links in the buffer.
+    memory_links: usize,
+}
+
+impl LinkSetState {
+    /// The links on disk.
+    fn disk_len(&self) -> usize {
+        self.runs.iter().map(|run| run.count as usize).sum()
+    }
+
+    /// The link is on disk.
+    fn disk_contains(&self, link: &str) -> io::Result<bool> {
+        let hashes = link_hashes(link);
+
+        if !self.bloom.contains(hashes) {
+            return Ok(false);
+        }
+
+        for run in self.runs.iter().rev() {
+            if run.contains(hashes.0, link)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Write the buffer to a new run and merge the runs of the same size.
+    fn flush(&mut self) -> io::Result<()> {
+        let bloom = &mut self.bloom;
+        let mut links: Vec<(u64, String, String)> = self
+            .buffer
+            .drain()
+            .map(|link| {
+                let hashes = link_hashes(link.inner());
+                bloom.insert(hashes);
+                (
+                    hashes.0,
+                    link.inner().to_ascii_lowercase().to_string(),
+                    link.inner().to_string(),
+                )
+            })
+            .collect();
+
+        links.sort_unstable_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        let count = links.len() as u64;
+        let mut links = links.into_iter();
+        let run = write_run(unique_path(&self.dir, &self.name, "run"), count, 0, || {
+            Ok(links.next().map(|(hash, _, link)| (hash, link)))
+        })?;
+
+        self.runs.push(run);
+
+        loop {
+            let len = self.runs.len();
+
+            if len < MERGE_RUNS
+                || !self.runs[len - MERGE_RUNS..]
+                    .iter()
+                    .all(|run| run.level == self.runs[len - 1].level)
+            {
+                break Ok(());
+            }
+
+            let runs = self.runs.split_off(len - MERGE_RUNS);
+            let merged = merge_runs(&self.dir, &self.name, &runs)?;
+
+            self.runs.push(merged);
+        }
+    }
+}
+
+/// Merge the runs into one run.
+fn merge_runs(dir: &Path, name: &str, runs: &[Run]) -> io::Result<Run> {
+    let mut readers = runs
+        .iter()
+        .map(|run| run.reader())
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heap = BinaryHeap::new();
+
+    for (position, reader) in readers.iter_mut().enumerate() {
+        match reader.next()? {
+            Some((hash, link)) => {
+                heap.push(Reverse((hash, link.to_ascii_lowercase(), position, link)))
+            }
+            _ => (),
+        }
+    }
+
+    write_run(
+        unique_path(dir, name, "run"),
+        runs.iter().map(|run| run.count).sum(),
+        runs.iter().map(|run| run.level).max().unwrap_or_default() + 1,
+        || match heap.pop() {
+            Some(Reverse((hash, _, position, link))) => {
+                match readers[position].next()? {
+                    Some((next_hash, next)) => heap.push(Reverse((
+                        next_hash,
+                        next.to_ascii_lowercase(),
+                        position,
+                        next,
+                    ))),
+                    _ => (),
+                }
+                Ok(Some((hash, link)))
+            }
+            _ => Ok(None),
+        },
+    )
+}
+
+/// The links of a disk link set read one at a time from memory and the run files.
+pub struct DiskLinks {
+    /// The links in memory.
+    buffer: hashbrown::hash_set::IntoIter<CaseInsensitiveString>,
+    /// The readers of the run files left.
+    readers: VecDeque<RunReader>,
+    /// The links removed from the run files.
+    removed: HashSet<CaseInsensitiveString>,
+    /// The run files taken from the set removed once the links are read.
+    _runs: Vec<Run>,
+}
+
+impl DiskLinks {
+    /// Read the links of the set state taking the links and the run files when drained.
+    fn new(state: &mut LinkSetState, drain: bool) -> Self {
+        let (buffer, runs, removed) = if drain {
+            state.bloom.clear();
+            (
+                std::mem::take(&mut state.buffer),
+                std::mem::take(&mut state.runs),
+                std::mem::take(&mut state.removed),
+            )
+        } else {
+            (state.buffer.clone(), Vec::new(), state.removed.clone())
+        };
+
+        let readers = match if drain { &runs } else { &state.runs }
+            .iter()
+            .map(|run| run.reader())
+            .collect::<io::Result<VecDeque<_>>>()
+        {
+            Ok(readers) => readers,
+            Err(e) => {
+                log::warn!("unable to read the links on disk: {}", e);
+                Default::default()
+            }
+        };
+
+        Self {
+            buffer: buffer.into_iter(),
+            readers,
+            removed,
+            _runs: runs,
+        }
+    }
+}
+
+impl Iterator for DiskLinks {
+    type Item = CaseInsensitiveString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.buffer.next() {
+            Some(link) => return Some(link),
+            _ => (),
+        }
+
+        while let Some(reader) = self.readers.front_mut() {
+            match reader.next() {
+                Ok(Some((_, link))) => {
+                    let link = CaseInsensitiveString::from(link);
+
+                    if !self.removed.contains(&link) {
+                        return Some(link);
+                    }
+                }
+                Ok(None) => {
+                    self.readers.pop_front();
+                }
+                Err(e) => {
+                    log::warn!("unable to read the links on disk: {}", e);
+                    self.readers.pop_front();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// A set of links kept on disk once the links in memory are over the limit. The links are looked up exactly in sorted run files with a Bloom filter in front.
+#[derive(Debug)]
+pub struct DiskLinkSet {
+    /// The links in memory and on disk.
+    state: Mutex<LinkSetState>,
+}
+
+impl DiskLinkSet {
+    /// A new empty set keeping the files in the directory.
+    pub fn new(
+        dir: &Path,
+        name: &str,
+        memory_links: usize,
+        expected_links: usize,
+    ) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        Ok(Self {
+            state: Mutex::new(LinkSetState {
+                dir: dir.to_path_buf(),
+                name: name.into(),
+                buffer: HashSet::new(),
+                runs: Vec::new(),
+                bloom: BloomFilter::new(expected_links),
+                removed: HashSet::new(),
+                memory_links: memory_links.max(1),
+            }),
+        })
+    }
+
+    /// The set has the link. Links that can not be read from disk are not found.
+    pub fn contains(&self, link: &CaseInsensitiveString) -> bool {
+        match self.state.lock() {
+            Ok(state) => {
+                state.buffer.contains(link)
+                    || !state.removed.contains(link)
+                        && match state.disk_contains(link.inner()) {
+                            Ok(found) => found,
+                            Err(e) => {
+                                log::warn!("unable to read the links on disk: {e}");
+                                false
+                            }
+                        }
+            }
+            _ => false,
+        }
+    }
+
+    /// Add the link returning `true` if the link is new.
+    pub fn insert(&self, link: CaseInsensitiveString) -> bool {
+        let added = !self.contains(&link);
+
+        if added {
+            self.extend([link]);
+        }
+
+        added
+    }
+
+    /// Add the links without checking the links on disk. The links should not be in the set.
+    pub fn extend(&self, links: impl IntoIterator<Item = CaseInsensitiveString>) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                for link in links {
+                    // a removed link is still in the run files.
+                    if state.removed.remove(&link) {
+                        continue;
+                    }
+
+                    state.buffer.insert(link);
+
+                    if state.buffer.len() >= state.memory_links {
+                        match state.flush() {
+                            Ok(_) => (),
+                            Err(e) => log::warn!("unable to write the links to disk: {e}"),
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Remove the link returning `true` if the link was in the set.
+    pub fn remove(&self, link: &CaseInsensitiveString) -> bool {
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.buffer.remove(link)
+                    || !state.removed.contains(link)
+                        && match state.disk_contains(link.inner()) {
+                            Ok(true) => state.removed.insert(link.clone()),
+                            Ok(_) => false,
+                            Err(e) => {
+                                log::warn!("unable to read the links on disk: {e}");
+                                false
+                            }
+                        }
+            }
+            _ => false,
+        }
+    }
+
+    /// The amount of links.
+    pub fn len(&self) -> usize {
+        match self.state.lock() {
+            Ok(state) => state.buffer.len() + state.disk_len() - state.removed.len(),
+            _ => 0,
+        }
+    }
+
+    /// The set has no links.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read the links from memory and disk one at a time. The links added after are not read.
+    pub fn iter(&self) -> Option<DiskLinks> {
+        self.state
+            .lock()
+            .ok()
+            .map(|mut state| DiskLinks::new(&mut state, false))
+    }
+
+    /// Take all the links reading them from memory and disk one at a time. The files are removed once the links are read.
+    pub fn drain(&self) -> Option<DiskLinks> {
+        self.state
+            .lock()
+            .ok()
+            .map(|mut state| DiskLinks::new(&mut state, true))
+    }
+
+    /// Remove all the links and the files.
+    pub fn clear(&self) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.buffer.clear();
+                state.runs.clear();
+                state.bloom.clear();
+                state.removed.clear();
+            }
+            _ => (),
+        }
+    }
+}
+
+/// The links of the frontier written to an append-only log in the order pushed.
+#[derive(Debug)]
+struct DiskQueue {
+    /// The path of the log.
+    path: PathBuf,
+    /// Append the links to the log.
+    writer: BufWriter<File>,
+    /// Read the links from the log.
+    reader: BufReader<File>,
+    /// The links not read yet.
+    len: usize,
+}
+
+impl DiskQueue {
+    /// A new empty log in the directory.
+    fn new(dir: &Path) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        let path = unique_path(dir, "frontier", "log");
+        let writer = BufWriter::new(File::create(&path)?);
+        let reader = BufReader::new(File::open(&path)?);
+
+        Ok(Self {
+            path,
+            writer,
+            reader,
+            len: 0,
+        })
+    }
+
+    /// Append the link.
+    fn push(&mut self, link: &FrontierLink) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "{}\t{}\t{}",
+            link.depth,
+            link.score,
+            link.url.inner()
+        )?;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Read the next link.
+    fn pop(&mut self) -> io::Result<Option<FrontierLink>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+
+        self.writer.flush()?;
+
+        let mut line = String::new();
+        self.reader.read_line(&mut line)?;
+        self.len -= 1;
+
+        if self.len == 0 {
+            self.writer.get_ref().set_len(0)?;
+            self.writer.get_mut().seek(SeekFrom::Start(0))?;
+            self.reader.seek(SeekFrom::Start(0))?;
+        }
+
+        Ok(parse_link(&line))
+    }
+
+    /// Read the links not taken yet one at a time.
+    fn iter(&mut self) -> io::Result<impl Iterator<Item = FrontierLink> + Send> {
+        self.writer.flush()?;
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.reader.stream_position()?))?;
+
+        Ok(BufReader::new(file)
+            .lines()
+            .take(self.len)
+            .filter_map(|line| line.ok().and_then(|line| parse_link(&line))))
+    }
+}
+
+impl Drop for DiskQueue {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Parse a frontier link of the log.
+fn parse_link(line: &str) -> Option<FrontierLink> {
+    let mut fields = line.trim_end_matches(['\r', '\n']).splitn(3, '\t');
+
+    let depth = fields.next()?.parse().ok()?;
+    let score = fields.next()?.parse().ok()?;
+    let url = fields.next()?;
+
+    Some(FrontierLink {
+        url: url.into(),
+        depth,
+        score,
+    })
+}
+
+/// A breadth first frontier keeping the links over the memory limit in a log on disk. The links on disk are taken in the order pushed once the links in memory run out.
+#[derive(Debug, Clone)]
+pub struct DiskFrontier {
+    /// The frontier of the links in memory.
+    frontier: BreadthFirst,
+    /// The links on disk shared with the clones.
+    queue: Arc<Mutex<DiskQueue>>,
+    /// The max amount of links in memory.
+    memory_links: usize,
+}
+
+impl DiskFrontier {
+    /// A new frontier keeping the links over the limit in the directory.
+    pub fn new(dir: &Path, memory_links: usize) -> io::Result<Self> {
+        Ok(Self {
+            frontier: BreadthFirst::default(),
+            queue: Arc::new(Mutex::new(DiskQueue::new(dir)?)),
+            memory_links: memory_links.max(1),
+        })
+    }
+
+    /// The links on disk.
+    fn disk_len(&self) -> usize {
+        match self.queue.lock() {
+            Ok(queue) => queue.len,
+            _ => 0,
+        }
+    }
+}
+
+impl Frontier for DiskFrontier {
+    fn push(&mut self, link: FrontierLink) {
+        if self.frontier.len() < self.memory_links && self.disk_len() == 0 {
+            self.frontier.push(link)
+        } else {
+            match self.queue.lock() {
+                Ok(mut queue) => match queue.push(&link) {
+                    Ok(_) => (),
+                    Err(e) => {
+                        log::warn!("unable to write the frontier to disk: {e}");
+                        self.frontier.push(link)
+                    }
+                },
+                _ => self.frontier.push(link),
+            }
+        }
+    }
+    fn pop(&mut self) -> Option<FrontierLink> {
+        if self.frontier.is_empty() {
+            match self.queue.lock() {
+                Ok(mut queue) => {
+                    while self.frontier.len() < self.memory_links {
+                        match queue.pop() {
+                            Ok(Some(link)) => self.frontier.push(link),
+                            Ok(None) => break,
+                            Err(e) => {
+                                log::warn!("unable to read the frontier from disk: {e}");
+                                break;
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        self.frontier.pop()
+    }
+    fn len(&self) -> usize {
+        self.frontier.len() + self.disk_len()
+    }
+    fn iter(&self) -> Box<dyn Iterator<Item = FrontierLink> + Send + '_> {
+        let disk = match self.queue.lock() {
+            Ok(mut queue) => match queue.iter() {
+                Ok(disk) => Some(disk),
+                Err(e) => {
+                    log::warn!("unable to read the frontier from disk: {}", e);
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        Box::new(self.frontier.iter().chain(disk.into_iter().flatten()))
+    }
+    fn round_size(&self) -> usize {
+        self.frontier.round_size().min(self.memory_links)
+    }
+}
+
+#[test]
+fn disk_link_set_dedup() {
+    let dir = std::env::temp_dir().join("spider_disk_link_set_test");
+    let set = DiskLinkSet::new(&dir, "visited", 3, 1000).unwrap();
+
+    for i in 0..50 {
+        assert!(set.insert(string_concat!("https://choosealicense.com/", i.to_string()).into()));
+    }
+
+    assert_eq!(set.len(), 50);
+    assert!(set.contains(&"https://CHOOSEALICENSE.com/7".into()));
+    assert!(set.contains(&"https://choosealicense.com/49".into()));
+    assert!(!set.contains(&"https://choosealicense.com/50".into()));
+    assert!(!set.insert("https://choosealicense.com/12".into()));
+    assert_eq!(set.iter().unwrap().count(), 50);
+    assert!(set.len() - set.state.lock().unwrap().buffer.len() >= 48);
+    assert!(set.state.lock().unwrap().runs.len() < MERGE_RUNS * 2);
+
+    assert!(set.remove(&"https://choosealicense.com/7".into()));
+    assert!(!set.remove(&"https://choosealicense.com/7".into()));
+    assert!(!set.contains(&"https://choosealicense.com/7".into()));
+    assert_eq!(set.len(), 49);
+    assert_eq!(set.iter().unwrap().count(), 49);
+    assert!(set.insert("https://choosealicense.com/7".into()));
+    assert!(set.contains(&"https://choosealicense.com/7".into()));
+    assert_eq!(set.len(), 50);
+
+    let drained: HashSet<CaseInsensitiveString> = set.drain().unwrap().collect();
+
+    assert_eq!(drained.len(), 50);
+    assert!(drained.contains::<CaseInsensitiveString>(&"https://choosealicense.com/12".into()));
+    assert!(set.is_empty());
+    assert!(set.insert("https://choosealicense.com/7".into()));
+
+    set.clear();
+
+    assert!(set.is_empty());
+    assert!(!set.contains(&"https://choosealicense.com/7".into()));
+}
+
+#[test]
+fn disk_frontier_order() {
+    let dir = std::env::temp_dir().join("spider_disk_frontier_test");
+    let mut frontier = DiskFrontier::new(&dir, 2).unwrap();
+
+    for i in 0..6 {
+        frontier.push(FrontierLink {
+            url: string_concat!("https://choosealicense.com/", i.to_string()).into(),
+            depth: i,
+            score: 0.5,
+        });
+    }
+
+    assert_eq!(frontier.len(), 6);
+    assert_eq!(frontier.disk_len(), 4);
+    assert_eq!(frontier.links().len(), 6);
+    assert_eq!(frontier.round_size(), 2);
+
+    let order: Vec<usize> = std::iter::from_fn(|| frontier.pop().map(|link| link.depth)).collect();
+
+    assert_eq!(order, vec![0, 1, 2, 3, 4, 5]);
+    assert!(frontier.is_empty());
+}
@@ -0,0 +1,251 @@
+use hashbrown::{HashMap, HashSet};
+use url::Url;
+
+/// The path params used to carry session ids in the url path like `;jsessionid=`.
+const SESSION_PARAMS: &[&str] = &[
+    "jsessionid",
+    "phpsessid",
+    "sessionid",
+    "session_id",
+    "sid",
+    "aspsessionid",
+];
+
+/// The rule that rejected a link as a crawler trap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrapRule {
+    /// A path segment is repeated too many times like `/a/a/a/a`.
+    #[default]
+    RepeatedSegment,
+    /// The url is too long.
+    UrlLength,
+    /// The url has too many query params.
+    QueryParams,
+    /// The url carries a session id in the path.
+    SessionId,
+    /// Too many urls share the same pattern like endless calendar pages.
+    PatternFamily,
+}
+
+/// Reject links that look like crawler traps with infinite url spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrapDetection {
+    /// The max times the same path segment can appear in a url.
+    pub max_repeated_segments: usize,
+    /// The max length of a url.
+    pub max_url_length: usize,
+    /// The max amount of query params of a url.
+    pub max_query_params: usize,
+    /// The max amount of urls with the same pattern. The pattern replaces numbers, dates and ids in the path with placeholders and drops the query values.
+    pub max_pattern_family: usize,
+    /// Reject urls with session ids in the path like `;jsessionid=`.
+    pub session_ids: bool,
+}
+
+impl Default for TrapDetection {
+    fn default() -> Self {
+        Self {
+            max_repeated_segments: 3,
+            max_url_length: 2048,
+            max_query_params: 12,
+            max_pattern_family: 1000,
+            session_ids: true,
+        }
+    }
+}
+
+/// The segment looks like a number, date or id.
+fn is_variable_segment(segment: &str) -> bool {
+    let digits = segment.chars().filter(|c| c.is_ascii_digit()).count();
+
+    digits > 0 && digits * 2 >= segment.len()
+        || segment.len() >= 16 && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// The pattern of the url with the variable path segments replaced and the query values dropped.
+fn pattern(url: &Url) -> String {
+    let mut pattern = url.host_str().unwrap_or_default().to_string();
+
+    match url.path_segments() {
+        Some(segments) => {
+            for segment in segments {
+                pattern.push('/');
+                if is_variable_segment(segment) {
+                    pattern.push_str("{}");
+                } else {
+                    pattern.push_str(segment);
+                }
+            }
+        }
+        _ => (),
+    }
+
+    let mut keys: Vec<String> = url.query_pairs().map(|(k, _)| k.into_owned()).collect();
+
+    if !keys.is_empty() {
+        keys.sort();
+        keys.dedup();
+        pattern.push('?');
+        pattern.push_str(&keys.join("&"));
+    }
+
+    pattern
+}
+
+/// Detect crawler traps counting the url patterns seen.
+#[derive(Debug, Clone, Default)]
+pub struct TrapDetector {
+    /// The trap detection rules.
+    detection: TrapDetection,
+    /// The amount of urls seen for each pattern.
+    families: HashMap<String, usize>,
+    /// The urls counted in the pattern families.
+    seen: HashSet<String>,
+}
+
+impl TrapDetector {
+    /// A new trap detector with the rules.
+    pub fn new(detection: TrapDetection) -> Self {
+        Self {
+            detection,
+            families: HashMap::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Check the url returning the rule that rejected it as a trap. Each url is counted once in its pattern family.
+    pub fn check(&mut self, url: &str) -> Option<TrapRule> {
+        if url.len() > self.detection.max_url_length {
+            return Some(TrapRule::UrlLength);
+        }
+
+        let link = url;
+
+        let url = match Url::parse(link) {
+            Ok(url) => url,
+            _ => return None,
+        };
+
+        if url.query_pairs().count() > self.detection.max_query_params {
+            return Some(TrapRule::QueryParams);
+        }
+
+        match url.path_segments() {
+            Some(segments) => {
+                let mut counts: HashMap<&str, usize> = HashMap::new();
+
+                for segment in segments.filter(|s| !s.is_empty()) {
+                    if self.detection.session_ids {
+                        match segment.split_once(';') {
+                            Some((_, param)) => {
+                                let key = param.split('=').next().unwrap_or_default();
+                                if SESSION_PARAMS.iter().any(|p| p.eq_ignore_ascii_case(key)) {
+                                    return Some(TrapRule::SessionId);
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+
+                    let count = counts.entry(segment).or_insert(0);
+                    *count += 1;
+
+                    if *count > self.detection.max_repeated_segments {
+                        return Some(TrapRule::RepeatedSegment);
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        if self.seen.contains(link) {
+            return None;
+        }
+
+        let family = self.families.entry(pattern(&url)).or_insert(0);
+
+        if *family >= self.detection.max_pattern_family {
+            Some(TrapRule::PatternFamily)
+        } else {
+            *family += 1;
+            self.seen.insert(link.to_string());
+            None
+        }
+    }
+
+    /// Remove the url from its pattern family when the link is not crawled.
+    pub fn release(&mut self, url: &str) {
+        if self.seen.remove(url) {
+            match Url::parse(url) {
+                Ok(url) => match self.families.get_mut(&pattern(&url)) {
+                    Some(family) => *family = family.saturating_sub(1),
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+    }
+
+    /// Remove the patterns seen.
+    pub fn clear(&mut self) {
+        self.families.clear();
+        self.seen.clear();
+    }
+}
+
+#[test]
+fn trap_rules() {
+    let mut detector = TrapDetector::new(TrapDetection {
+        max_pattern_family: 2,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        detector.check("https://choosealicense.com/a/a/a/a/licenses/"),
+        Some(TrapRule::RepeatedSegment)
+    );
+    assert_eq!(
+        detector.check(&string_concat!(
+            "https://choosealicense.com/",
+            "a".repeat(2048)
+        )),
+        Some(TrapRule::UrlLength)
+    );
+    assert_eq!(
+        detector.check(
+            "https://choosealicense.com/?a=1&b=2&c=3&d=4&e=5&f=6&g=7&h=8&i=9&j=10&k=11&l=12&m=13"
+        ),
+        Some(TrapRule::QueryParams)
+    );
+    assert_eq!(
+        detector.check("https://choosealicense.com/licenses/;jsessionid=4f6a2b"),
+        Some(TrapRule::SessionId)
+    );
+    assert_eq!(
+        detector.check("https://choosealicense.com/calendar/2024/01?view=month"),
+        None
+    );
+    assert_eq!(
+        detector.check("https://choosealicense.com/calendar/2024/01?view=month"),
+        None
+    );
+    assert_eq!(
+        detector.check("https://choosealicense.com/calendar/2024/02?view=month"),
+        None
+    );
+    assert_eq!(
+        detector.check("https://choosealicense.com/calendar/2024/03?view=month"),
+        Some(TrapRule::PatternFamily)
+    );
+    detector.release("https://choosealicense.com/calendar/2024/02?view=month");
+    assert_eq!(
+        detector.check("https://choosealicense.com/calendar/2024/03?view=month"),
+        None
+    );
+    assert_eq!(
+        detector.check("https://choosealicense.com/licenses/mit/"),
+        None
+    );
+}
@@ -4,7 +4,7 @@
 //! that has improvements that help our case for speed.
 //!
 //! The robots.txt Exclusion Protocol is implemented as specified in
-//! <http://www.robotstxt.org/norobots-rfc.txt>
+//! <https://www.rfc-editor.org/rfc/rfc9309.html>
 //!
 //!
 //! Add ``extern crate robotparser`` to your crate root and your're good to go!
@@ -27,36 +27,22 @@
 
 use crate::Client;
 use compact_str::CompactString;
-#[cfg(feature = "regex")]
-use hashbrown::HashSet;
-#[cfg(feature = "regex")]
-use regex::RegexSet;
+use reqwest::header::LOCATION;
 use reqwest::Response;
 use reqwest::StatusCode;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A rule line is a single "Allow:" (allowance==True) or "Disallow:"
-/// (allowance==False) followed by a path."""
+/// (allowance==False) followed by a path pattern. The pattern may use `*` to
+/// match any characters and `$` to match the end of the path.
 #[derive(Debug, Eq, PartialEq, Clone)]
-#[cfg(not(feature = "regex"))]
 struct RuleLine {
-    /// Path of the rule
+    /// Path pattern of the rule normalized with `normalize_path`.
     path: String,
     /// Is the rule allowed?
     allowance: bool,
 }
 
-/// A rule line is a single "Allow:" (allowance==True) or "Disallow:"
-/// (allowance==False) followed by a path."""
-#[derive(Debug, Clone)]
-#[cfg(feature = "regex")]
-struct RuleLine {
-    /// Path of the rule
-    path: Option<regex::Regex>,
-    /// Is the rule allowed?
-    allowance: bool,
-}
-
 #[derive(Debug, Eq, PartialEq, Clone)]
 /// Determine the amount of request allowed between navigation or crawls.
 pub struct RequestRate {
@@ -66,11 +52,10 @@ pub struct RequestRate {
     pub seconds: usize,
 }
 
-/// An entry has one or more user-agents and zero or more rulelines
-#[derive(Debug, Clone)]
-#[cfg_attr(not(feature = "regex"), derive(Eq, PartialEq))]
+/// An entry is a group with one or more user-agents and zero or more rulelines
+#[derive(Debug, Clone, Eq, PartialEq)]
 struct Entry {
-    /// Multiple user agents to use
+    /// The lowercase product tokens of the user agents or `*`
     useragents: Vec<String>,
     /// Rules that should be ignored
     rulelines: Vec<RuleLine>,
@@ -81,80 +66,124 @@ struct Entry {
 }
 
 /// robots.txt file parser
-#[derive(Debug, Clone)]
-#[cfg_attr(not(feature = "regex"), derive(Eq, PartialEq))]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct RobotFileParser {
-    /// Entire robots.txt list of urls
+    /// The groups of the robots.txt in order.
     entries: Vec<Entry>,
-    /// Base entry to list
-    default_entry: Entry,
     /// Dis-allow links reguardless of robots.txt
     disallow_all: bool,
     /// Allow links reguardless of robots.txt
     allow_all: bool,
     /// Time last checked robots.txt file
     last_checked: i64,
-    /// Disallow list of regex paths to ignore.
-    #[cfg(feature = "regex")]
-    disallow_paths_regex: RegexSet,
-    /// Disallow list of paths to ignore.
-    #[cfg(feature = "regex")]
-    disallow_paths: HashSet<String>,
-    /// Disallow list of regex agents to ignore.
-    #[cfg(feature = "regex")]
-    disallow_agents_regex: RegexSet,
-    /// Wild card agent provided.
-    #[cfg(feature = "regex")]
-    wild_card_agent: bool,
-    /// Disallow list of agents to ignore.
-    #[cfg(feature = "regex")]
-    disallow_agents: HashSet<String>,
+    /// The sitemaps advertised with `Sitemap:` lines.
+    sitemaps: Vec<String>,
 }
 
-impl RuleLine {
-    #[cfg(feature = "regex")]
-    fn new(path: &str, allowance: bool) -> RuleLine {
-        use regex::Regex;
+/// The max consecutive redirects followed for the robots.txt.
+const MAX_REDIRECTS: usize = 5;
 
-        RuleLine {
-            path: match Regex::new(path) {
-                Ok(r) => Some(r),
-                _ => None,
-            },
-            allowance: path.is_empty() && !allowance || allowance,
+/// Uppercase hex digits for percent-encoding.
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The value of a hex digit.
+fn hex_value(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|v| v as u8)
+}
+
+/// Normalize a path or pattern for matching. The octets outside ASCII are
+/// percent-encoded, the percent-encoded unreserved characters are decoded and
+/// the hex digits are uppercased so equal paths compare equal.
+fn normalize_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut normalized = String::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+
+        match (
+            c,
+            bytes.get(i + 1).copied().and_then(hex_value),
+            bytes.get(i + 2).copied().and_then(hex_value),
+        ) {
+            (b'%', Some(high), Some(low)) => {
+                let decoded = high * 16 + low;
+
+                if decoded.is_ascii_alphanumeric() || b"-._~".contains(&decoded) {
+                    normalized.push(decoded as char);
+                } else {
+                    normalized.push('%');
+                    normalized.push(HEX[high as usize] as char);
+                    normalized.push(HEX[low as usize] as char);
+                }
+                i += 3;
+            }
+            _ => {
+                if c.is_ascii() {
+                    normalized.push(c as char);
+                } else {
+                    normalized.push('%');
+                    normalized.push(HEX[(c >> 4) as usize] as char);
+                    normalized.push(HEX[(c & 15) as usize] as char);
+                }
+                i += 1;
+            }
         }
     }
 
-    #[cfg(not(feature = "regex"))]
-    fn new(path: &str, allowance: bool) -> RuleLine {
-        RuleLine {
-            path: path.into(),
-            allowance: path.is_empty() && !allowance || allowance,
+    normalized
+}
+
+/// Match the path against the pattern from the start of the path. `*` matches
+/// any characters and a trailing `$` matches the end of the path.
+fn path_match(path: &[u8], pattern: &[u8]) -> bool {
+    // the positions in the path the pattern matched up to
+    let mut positions = vec![0];
+
+    for (i, c) in pattern.iter().enumerate() {
+        match c {
+            b'$' if i + 1 == pattern.len() => return positions.contains(&path.len()),
+            b'*' => positions = (positions[0]..=path.len()).collect(),
+            _ => {
+                positions = positions
+                    .into_iter()
+                    .filter(|p| path.get(*p) == Some(c))
+                    .map(|p| p + 1)
+                    .collect();
+
+                if positions.is_empty() {
+                    return false;
+                }
+            }
         }
     }
 
-    #[cfg(not(feature = "regex"))]
-    fn applies_to(&self, pathname: &str) -> bool {
-        if self.path == "*" {
-            true
-        } else if self.path == "/" && pathname == "/" {
-            true
-        } else if self.path.ends_with("/") && pathname.starts_with(&self.path) {
-            true
-        } else {
-            self.path
-                .strip_suffix('*')
-                .map_or(false, |prefix| pathname.starts_with(prefix))
-                || pathname == self.path
+    true
+}
+
+/// The product token of a user agent like `FooBot` for `FooBot/2.1`. The
+/// token is the leading letters, `_` and `-`.
+pub(crate) fn product_token(useragent: &str) -> &str {
+    let useragent = useragent.trim_start();
+    let end = useragent
+        .find(|c: char| !(c.is_ascii_alphabetic() || c == '_' || c == '-'))
+        .unwrap_or(useragent.len());
+
+    &useragent[..end]
+}
+
+impl RuleLine {
+    fn new(path: &str, allowance: bool) -> RuleLine {
+        RuleLine {
+            path: normalize_path(path),
+            allowance,
         }
     }
 
-    #[cfg(feature = "regex")]
+    /// The rule matches the normalized path.
     fn applies_to(&self, pathname: &str) -> bool {
-        match self.path {
-            Some(ref regex) => regex.is_match(pathname),
-            _ => false,
-        }
+        path_match(pathname.as_bytes(), self.path.as_bytes())
     }
 }
 
@@ -169,38 +198,24 @@ impl Entry {
         }
     }
 
-    /// check if this entry applies to the specified agent
-    fn applies_to(&self, useragent: &str) -> bool {
-        let ua = useragent
-            .split('/')
-            .nth(0)
-            .unwrap_or_default()
-            .to_lowercase();
+    /// check if this entry names the lowercase product token
+    fn applies_to(&self, token: &str) -> bool {
+        self.useragents.iter().any(|agent| agent == token)
+    }
 
-        for agent in &self.useragents {
-            if agent == "*" || ua.contains(agent) {
-                return true;
-            }
-        }
+    /// Add the product token of the user agent line to the list
+    fn push_useragent(&mut self, useragent: &str) {
+        let useragent = useragent.trim();
 
-        false
-    }
+        if useragent.split_whitespace().next() == Some("*") {
+            self.useragents.push("*".into());
+        } else {
+            let token = product_token(useragent);
 
-    /// Preconditions:
-    /// - our agent applies to this entry
-    /// - filename is URL decoded
-    fn allowance(&self, filename: &str) -> bool {
-        for line in &self.rulelines {
-            if line.applies_to(filename) {
-                return line.allowance;
+            if !token.is_empty() {
+                self.useragents.push(token.to_ascii_lowercase());
             }
         }
-        true
-    }
-
-    /// Add to user agent list
-    fn push_useragent(&mut self, useragent: &str) {
-        self.useragents.push(useragent.to_lowercase());
     }
 
     /// Add rule to list
@@ -208,16 +223,11 @@ impl Entry {
         self.rulelines.push(ruleline);
     }
 
-    /// Determine if user agent exist
+    /// Determine if the wild card user agent exist
     fn has_useragent(&self) -> bool {
         self.useragents.iter().any(|a| a == "*")
     }
 
-    /// Is the user-agent list empty?
-    fn is_empty(&self) -> bool {
-        self.useragents.is_empty() && self.rulelines.is_empty()
-    }
-
     /// Set the crawl delay for the website
     fn set_crawl_delay(&mut self, delay: Duration) {
         self.crawl_delay = Some(delay);
@@ -245,7 +255,7 @@ impl Default for Entry {
     }
 }
 
-/// extract the path of a string
+/// extract the path with the query of a string
 fn extract_path(url: &str) -> &str {
     if !url.is_empty() {
         let prefix = if url.starts_with("https://") {
@@ -261,8 +271,8 @@ fn extract_path(url: &str) -> &str {
         if let Some(path_start) = url_slice.find('/') {
             let path = &url_slice[path_start..];
 
-            if let Some(query_start) = path.find('?') {
-                &path[..query_start]
+            if let Some(fragment_start) = path.find('#') {
+                &path[..fragment_start]
             } else {
                 path
             }
@@ -276,32 +286,13 @@ fn extract_path(url: &str) -> &str {
 
 impl RobotFileParser {
     /// Establish a new robotparser for a website domain
-    #[cfg(not(feature = "regex"))]
     pub fn new() -> Box<RobotFileParser> {
         RobotFileParser {
             entries: vec![],
-            default_entry: Entry::new(),
             disallow_all: false,
             allow_all: false,
             last_checked: 0i64,
-        }
-        .into()
-    }
-
-    /// Establish a new robotparser for a website domain
-    #[cfg(feature = "regex")]
-    pub fn new() -> Box<RobotFileParser> {
-        RobotFileParser {
-            entries: vec![],
-            default_entry: Entry::new(),
-            disallow_all: false,
-            disallow_paths_regex: RegexSet::default(),
-            disallow_agents_regex: RegexSet::default(),
-            disallow_paths: Default::default(),
-            disallow_agents: Default::default(),
-            wild_card_agent: false,
-            allow_all: false,
-            last_checked: 0i64,
+            sitemaps: vec![],
         }
         .into()
     }
@@ -325,35 +316,76 @@ impl RobotFileParser {
         }
     }
 
-    /// Reads the robots.txt URL and feeds it to the parser.
+    /// Reads the robots.txt URL and feeds it to the parser. The rules of an
+    /// earlier read are replaced. Up to five redirects are followed. The
+    /// robots.txt is unavailable and all the urls are allowed for a 4xx
+    /// status or too many redirects. The robots.txt is unreachable and all
+    /// the urls are disallowed for a 5xx status or a network error.
     pub async fn read(&mut self, client: &Client, url: &str) {
         self.modified();
+        self.entries.clear();
+        self.sitemaps.clear();
+        self.allow_all = false;
+        self.disallow_all = false;
+
+        let mut target = string_concat!(url, "robots.txt");
+
+        for _ in 0..=MAX_REDIRECTS {
+            let res = match client.get(&target).send().await {
+                Ok(res) => res,
+                Err(_) => {
+                    self.disallow_all = true;
+                    return;
+                }
+            };
+            let status = res.status();
+
+            if status.is_redirection() {
+                match res
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .and_then(|location| res.url().join(location).ok())
+                {
+                    Some(location) => {
+                        target = location.into();
+                        continue;
+                    }
+                    _ => (),
+                }
+            }
 
-        let request = client.get(string_concat!(url, "robots.txt"));
-
-        let res = match request.send().await {
-            Ok(res) => res,
-            Err(_) => {
-                return;
+            if self.set_status(status) {
+                self.from_response(res).await;
             }
-        };
-        let status = res.status();
 
-        match status {
-            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            return;
+        }
+
+        self.allow_all = true;
+    }
+
+    /// Set the access for the status code of the robots.txt response. Returns
+    /// `true` when the body should be parsed.
+    fn set_status(&mut self, status: StatusCode) -> bool {
+        if status.is_success() {
+            true
+        } else {
+            if status.is_server_error() {
                 self.disallow_all = true;
-            }
-            status
-                if status >= StatusCode::BAD_REQUEST
-                    && status < StatusCode::INTERNAL_SERVER_ERROR =>
-            {
+            } else {
                 self.allow_all = true;
             }
-            StatusCode::OK => self.from_response(res).await,
-            _ => (),
+            false
         }
     }
 
+    /// The robots.txt could not be reached due to a server or network error.
+    /// All the urls are disallowed until the robots.txt is read again.
+    pub fn is_unreachable(&self) -> bool {
+        self.disallow_all
+    }
+
     /// Reads the HTTP response and feeds it to the parser.
     pub async fn from_response(&mut self, response: Response) {
         match response.text().await {
@@ -369,13 +401,7 @@ impl RobotFileParser {
     }
 
     fn _add_entry(&mut self, entry: Entry) {
-        if entry.has_useragent() {
-            // the default entry is considered last
-            if self.default_entry.is_empty() {
-                // the first default entry wins
-                self.default_entry = entry;
-            }
-        } else {
+        if !entry.useragents.is_empty() {
             self.entries.push(entry);
         }
     }
@@ -383,12 +409,11 @@ impl RobotFileParser {
     ///
     /// Parse the input lines from a robots.txt file
     ///
-    /// We allow that a user-agent: line is not preceded by
-    /// one or more blank lines.
+    /// A group starts with one or more user-agent lines followed by the
+    /// rules. Blank lines and lines outside of the groups like sitemap do
+    /// not end a group.
     ///
     pub fn parse<T: AsRef<str>>(&mut self, lines: &[T]) {
-        use percent_encoding::percent_decode;
-
         // states:
         //   0: start state
         //   1: saw user-agent line
@@ -397,21 +422,7 @@ impl RobotFileParser {
         let mut entry = Entry::new();
 
         for line in lines {
-            let mut ln = line.as_ref();
-            if ln.is_empty() {
-                match state {
-                    1 => {
-                        entry = Entry::new();
-                        state = 0;
-                    }
-                    2 => {
-                        self._add_entry(entry);
-                        entry = Entry::new();
-                        state = 0;
-                    }
-                    _ => {}
-                }
-            }
+            let mut ln = line.as_ref().trim_start_matches('\u{feff}');
             // remove optional comment and strip line
             if let Some(i) = ln.find('#') {
                 ln = &ln[0..i];
@@ -420,123 +431,98 @@ impl RobotFileParser {
             if ln.is_empty() {
                 continue;
             }
-            let parts: Vec<&str> = ln.splitn(2, ':').collect();
-
-            if parts.len() == 2 {
-                let part0 = parts[0].trim().to_lowercase();
-                let part1 = String::from_utf8(percent_decode(parts[1].trim().as_bytes()).collect())
-                    .unwrap_or_default();
-                match part0 {
-                    ref x if x.to_lowercase() == "user-agent" => {
+            let parts: Option<(&str, &str)> = match ln.split_once(':') {
+                Some(parts) => Some(parts),
+                // accept a missing colon when the key and value are split by whitespace
+                _ => ln
+                    .split_once(char::is_whitespace)
+                    .filter(|(_, value)| !value.trim().contains(char::is_whitespace)),
+            };
+
+            if let Some((key, value)) = parts {
+                let value = value.trim();
+
+                match key.trim().to_lowercase().as_str() {
+                    "user-agent" | "useragent" | "user agent" => {
                         if state == 2 {
                             self._add_entry(entry);
                             entry = Entry::new();
                         }
-                        entry.push_useragent(&part1);
+                        entry.push_useragent(value);
                         state = 1;
-                        self.set_disallow_agents_list(&part1);
                     }
-                    ref x if x.to_lowercase() == "disallow" => {
-                        if state != 0 {
-                            entry.push_ruleline(RuleLine::new(&part1, false));
-                            state = 2;
-                            self.set_disallow_list(&part1);
+                    "disallow" | "dissallow" | "dissalow" | "disalow" | "diasllow" | "disallaw"
+                        if state != 0 =>
+                    {
+                        if !value.is_empty() {
+                            entry.push_ruleline(RuleLine::new(value, false));
                         }
+                        state = 2;
                     }
-                    ref x if x.to_lowercase() == "allow" => {
-                        if state != 0 {
-                            entry.push_ruleline(RuleLine::new(&part1, true));
-                            state = 2;
+                    "allow" if state != 0 => {
+                        if !value.is_empty() {
+                            entry.push_ruleline(RuleLine::new(value, true));
                         }
+                        state = 2;
                     }
-                    ref x if x.to_lowercase() == "crawl-delay" => {
-                        if state != 0 {
-                            if let Ok(delay) = part1.parse::<f64>() {
-                                let delay_seconds = delay.trunc();
-                                let delay_nanoseconds = delay.fract() * 10f64.powi(9);
-                                let delay =
-                                    Duration::new(delay_seconds as u64, delay_nanoseconds as u32);
-                                entry.set_crawl_delay(delay);
-                            }
-                            state = 2;
+                    "crawl-delay" if state != 0 => {
+                        if let Ok(delay) = value.parse::<f64>() {
+                            let delay_seconds = delay.trunc();
+                            let delay_nanoseconds = delay.fract() * 10f64.powi(9);
+                            let delay =
+                                Duration::new(delay_seconds as u64, delay_nanoseconds as u32);
+                            entry.set_crawl_delay(delay);
                         }
+                        state = 2;
                     }
-                    ref x if x.to_lowercase() == "sitemap" => {
-                        if state != 0 {
-                            state = 2;
-                        }
+                    "sitemap" | "site-map"
+                        if !value.is_empty() && !self.sitemaps.iter().any(|s| s == value) =>
+                    {
+                        self.sitemaps.push(value.into());
                     }
-                    ref x if x.to_lowercase() == "request-rate" => {
-                        if state != 0 {
-                            let numbers: Vec<Result<usize, _>> =
-                                part1.split('/').map(|x| x.parse::<usize>()).collect();
-                            if numbers.len() == 2 && numbers[0].is_ok() && numbers[1].is_ok() {
-                                let req_rate = RequestRate {
-                                    requests: numbers[0].clone().unwrap(),
-                                    seconds: numbers[1].clone().unwrap(),
-                                };
-                                entry.set_req_rate(req_rate);
-                            }
-                            state = 2;
+                    "request-rate" if state != 0 => {
+                        let numbers: Vec<Result<usize, _>> =
+                            value.split('/').map(|x| x.parse::<usize>()).collect();
+                        if numbers.len() == 2 && numbers[0].is_ok() && numbers[1].is_ok() {
+                            let req_rate = RequestRate {
+                                requests: numbers[0].clone().unwrap(),
+                                seconds: numbers[1].clone().unwrap(),
+                            };
+                            entry.set_req_rate(req_rate);
                         }
+                        state = 2;
                     }
                     _ => {}
                 }
             }
         }
 
-        if state == 2 {
+        if state != 0 {
             self._add_entry(entry);
         }
-
-        self.build_disallow_list()
     }
 
-    /// Include the disallow paths in the regex set. This does nothing without the 'regex' feature.
-    #[cfg(not(feature = "regex"))]
-    pub fn set_disallow_list(&mut self, _path: &str) {}
-
-    /// Include the disallow  paths in the regex set. This does nothing without the 'regex' feature.
-    #[cfg(feature = "regex")]
-    pub fn set_disallow_list(&mut self, path: &str) {
-        if !path.is_empty() {
-            self.disallow_paths.insert(path.into());
-        }
-    }
-
-    /// Include the disallow agents in the regex set. This does nothing without the 'regex' feature.
-    #[cfg(not(feature = "regex"))]
-    pub fn set_disallow_agents_list(&mut self, _agent: &str) {}
-
-    /// Include the disallow agents in the regex set. This does nothing without the 'regex' feature.
-    #[cfg(feature = "regex")]
-    pub fn set_disallow_agents_list(&mut self, agent: &str) {
-        if !agent.is_empty() {
-            if agent == "*" {
-                self.wild_card_agent = true;
-            }
-            self.disallow_agents.insert(agent.into());
-        }
-    }
-
-    /// Build the regex disallow list. This does nothing without the 'regex' feature.
-    #[cfg(not(feature = "regex"))]
-    pub fn build_disallow_list(&mut self) {}
+    /// The groups that apply to the user agent. All the groups naming the
+    /// product token of the user agent are combined. The `*` groups are used
+    /// when no group names the product token.
+    fn entries_for(&self, useragent: &str) -> Vec<&Entry> {
+        let token = product_token(useragent).to_ascii_lowercase();
+        let entries: Vec<&Entry> = if token.is_empty() {
+            vec![]
+        } else {
+            self.entries
+                .iter()
+                .filter(|entry| entry.applies_to(&token))
+                .collect()
+        };
 
-    /// Build the regex disallow list. This does nothing without the 'regex' feature.
-    #[cfg(feature = "regex")]
-    pub fn build_disallow_list(&mut self) {
-        if !self.disallow_paths.is_empty() {
-            match RegexSet::new(&self.disallow_paths) {
-                Ok(s) => self.disallow_paths_regex = s,
-                _ => (),
-            }
-        }
-        if !self.disallow_agents.is_empty() {
-            match RegexSet::new(&self.disallow_agents) {
-                Ok(s) => self.disallow_agents_regex = s,
-                _ => (),
-            }
+        if entries.is_empty() {
+            self.entries
+                .iter()
+                .filter(|entry| entry.has_useragent())
+                .collect()
+        } else {
+            entries
         }
     }
 
@@ -551,45 +537,37 @@ impl RobotFileParser {
         } else if self.last_checked == 0 || self.disallow_all {
             false
         } else {
-            // search for given user agent matches
-            // the first match counts
-            let url_str = extract_path(&url);
-
-            if self.entry_allowed(&useragent, url_str) {
-                true
-            } else {
-                // try the default entry last
-                let default_entry = &self.default_entry;
+            let url_str = extract_path(url);
 
-                if !default_entry.is_empty() {
-                    default_entry.allowance(url_str)
-                } else {
-                    // agent not found ==> access granted
-                    true
-                }
-            }
+            // the robots.txt file itself is always allowed
+            url_str == "/robots.txt" || self.entry_allowed(&useragent, url_str)
         }
     }
 
-    /// Is the entry apply to the robots.txt?
-    #[cfg(not(feature = "regex"))]
+    /// Is the path allowed for the user agent? The rule with the longest
+    /// matching pattern wins and allow wins when the patterns are the same
+    /// length. Paths without a matching rule are allowed.
     pub fn entry_allowed<T: AsRef<str>>(&self, useragent: &T, url_str: &str) -> bool {
-        for entry in &self.entries {
-            if entry.applies_to(useragent.as_ref()) {
-                return entry.allowance(url_str);
+        let path = normalize_path(url_str);
+        let mut matched: Option<(usize, bool)> = None;
+
+        for entry in self.entries_for(useragent.as_ref()) {
+            for line in &entry.rulelines {
+                if line.applies_to(&path) {
+                    let rule = (line.path.len(), line.allowance);
+
+                    match matched {
+                        Some(m) if rule <= m => (),
+                        _ => matched = Some(rule),
+                    }
+                }
             }
         }
-        false
-    }
-
-    /// Is the entry apply to the robots.txt?
-    #[cfg(feature = "regex")]
-    pub fn entry_allowed<T: AsRef<str>>(&self, useragent: &T, url_str: &str) -> bool {
-        let agent_checked =
-            self.wild_card_agent || self.disallow_agents_regex.is_match(useragent.as_ref());
-        let disallow = agent_checked && self.disallow_paths_regex.is_match(url_str);
 
-        !disallow
+        match matched {
+            Some((_, allowance)) => allowance,
+            _ => true,
+        }
     }
 
     /// Returns the crawl delay for this user agent as a `Duration`, or None if no crawl delay is defined.
@@ -597,44 +575,258 @@ impl RobotFileParser {
         if self.last_checked == 0 {
             None
         } else {
-            let useragent = useragent.as_ref();
-            let crawl_delay: Option<Duration> = match useragent {
-                Some(ua) => {
-                    for entry in &self.entries {
-                        if entry.applies_to(ua) {
-                            return entry.get_crawl_delay();
-                        }
-                    }
-                    None
-                }
-                _ => None,
+            let useragent = match useragent {
+                Some(ua) => ua.as_str(),
+                _ => "*",
             };
 
-            if crawl_delay.is_some() {
-                crawl_delay
-            } else {
-                let default_entry = &self.default_entry;
-
-                if !default_entry.is_empty() {
-                    return default_entry.get_crawl_delay();
-                }
-
-                None
-            }
+            self.entries_for(useragent)
+                .iter()
+                .find_map(|entry| entry.get_crawl_delay())
         }
     }
 
+    /// Returns the sitemap urls advertised with `Sitemap:` lines in the order found.
+    pub fn get_sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+
     /// Returns the request rate for this user agent as a `RequestRate`, or None if not request rate is defined
     pub fn get_req_rate<T: AsRef<str>>(&self, useragent: T) -> Option<RequestRate> {
-        let useragent = useragent.as_ref();
         if self.last_checked == 0 {
             return None;
         }
-        for entry in &self.entries {
-            if entry.applies_to(useragent) {
-                return entry.get_req_rate();
-            }
-        }
-        None
+
+        self.entries_for(useragent.as_ref())
+            .iter()
+            .find_map(|entry| entry.get_req_rate())
     }
 }
+
+#[cfg(test)]
+/// A parser for the robots.txt content.
+fn robots(content: &str) -> Box<RobotFileParser> {
+    let mut parser = RobotFileParser::new();
+    parser.modified();
+    parser.parse(&content.lines().collect::<Vec<_>>());
+    parser
+}
+
+#[test]
+fn robots_rfc9309_example() {
+    let parser = robots(
+        "User-Agent: *\nDisallow: *.gif$\nDisallow: /example/\nAllow: /publications/\n\nUser-Agent: foobot\nDisallow:/\nAllow:/example/page.html\nAllow:/example/allowed.gif\n\nUser-Agent: barbot\nUser-Agent: bazbot\nDisallow: /example/page.html\n\nUser-Agent: quxbot\n",
+    );
+    let fetch = |agent: &str, path: &str| {
+        parser.can_fetch(agent, &string_concat!("https://example.com", path))
+    };
+
+    assert!(fetch("foobot/1.0", "/example/page.html"));
+    assert!(fetch("FooBot", "/example/allowed.gif"));
+    assert!(!fetch("foobot", "/example/other.html"));
+    assert!(!fetch("barbot", "/example/page.html"));
+    assert!(fetch("bazbot", "/example/other.html"));
+    assert!(fetch("bazbot", "/image.gif"));
+    assert!(fetch("quxbot", "/example/page.html"));
+    assert!(!fetch("otherbot", "/example/page.html"));
+    assert!(!fetch("otherbot", "/images/image.gif"));
+    assert!(fetch("otherbot", "/images/image.gif?size=2"));
+    assert!(fetch("otherbot", "/publications/"));
+    assert!(fetch("*", "/robots.txt"));
+}
+
+#[test]
+fn robots_longest_match() {
+    let allowed = |content: &str, path: &str| {
+        robots(content).can_fetch("FooBot", &string_concat!("https://example.com", path))
+    };
+
+    assert!(!allowed(
+        "user-agent: FooBot\ndisallow: /x/page.html\nallow: /x/\n",
+        "/x/page.html"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\nallow: /x/page.html\ndisallow: /x/\n",
+        "/x/page.html"
+    ));
+    assert!(!allowed(
+        "user-agent: FooBot\nallow: /x/page.html\ndisallow: /x/\n",
+        "/x/"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\ndisallow: \nallow: \n",
+        "/x/page.html"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\ndisallow: /\nallow: /\n",
+        "/x/page.html"
+    ));
+    assert!(!allowed(
+        "user-agent: FooBot\ndisallow: /x\nallow: /x/\n",
+        "/x"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\ndisallow: /x\nallow: /x/\n",
+        "/x/"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\ndisallow: /x/page.html\nallow: /x/page.html\n",
+        "/x/page.html"
+    ));
+    assert!(!allowed(
+        "user-agent: FooBot\nallow: /page\ndisallow: /*.html\n",
+        "/page.html"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\nallow: /page\ndisallow: /*.html\n",
+        "/page"
+    ));
+    assert!(allowed(
+        "user-agent: FooBot\nallow: /x/page.\ndisallow: /*.html\n",
+        "/x/page.html"
+    ));
+    assert!(!allowed(
+        "user-agent: FooBot\nallow: /x/page.\ndisallow: /*.html\n",
+        "/x/y.html"
+    ));
+    assert!(allowed(
+        "User-agent: *\nDisallow: /x/\nUser-agent: FooBot\nDisallow: /y/\n",
+        "/x/page"
+    ));
+    assert!(!allowed(
+        "User-agent: *\nDisallow: /x/\nUser-agent: FooBot\nDisallow: /y/\n",
+        "/y/page"
+    ));
+}
+
+#[test]
+fn robots_wildcards() {
+    let allowed = |content: &str, path: &str| {
+        robots(&string_concat!("user-agent: FooBot\n", content))
+            .can_fetch("FooBot", &string_concat!("https://example.com", path))
+    };
+
+    assert!(allowed("allow: /$\ndisallow: /\n", "/"));
+    assert!(!allowed("allow: /$\ndisallow: /\n", "/page.html"));
+
+    for path in [
+        "/fish",
+        "/fish.html",
+        "/fish/salmon.html",
+        "/fishheads",
+        "/fishheads/yummy.html",
+        "/fish.php?id=anything",
+    ] {
+        assert!(!allowed("disallow: /fish\n", path), "{}", path);
+        assert!(!allowed("disallow: /fish*\n", path), "{}", path);
+    }
+    for path in ["/Fish.asp", "/catfish", "/?id=fish", "/desert/fish"] {
+        assert!(allowed("disallow: /fish\n", path), "{}", path);
+    }
+
+    assert!(allowed("disallow: /fish/\n", "/fish"));
+    assert!(!allowed("disallow: /fish/\n", "/fish/salmon.htm"));
+    assert!(allowed("disallow: /fish/\n", "/fish.html"));
+
+    assert!(!allowed("disallow: /*.php\n", "/index.php"));
+    assert!(!allowed(
+        "disallow: /*.php\n",
+        "/folder/filename.php?parameters"
+    ));
+    assert!(allowed("disallow: /*.php\n", "/windows.PHP"));
+
+    assert!(!allowed("disallow: /*.php$\n", "/filename.php"));
+    assert!(!allowed("disallow: /*.php$\n", "/folder/filename.php"));
+    assert!(allowed("disallow: /*.php$\n", "/filename.php?parameters"));
+    assert!(allowed("disallow: /*.php$\n", "/filename.php5"));
+
+    assert!(!allowed("disallow: /fish*.php\n", "/fish.php"));
+    assert!(!allowed(
+        "disallow: /fish*.php\n",
+        "/fishheads/catfish.php?parameters"
+    ));
+    assert!(allowed("disallow: /fish*.php\n", "/Fish.PHP"));
+
+    assert!(!allowed("disallow: /path?q=a.b\n", "/path?q=a.b"));
+    assert!(allowed("disallow: /path?q=a.b\n", "/pathq=aXb"));
+}
+
+#[test]
+fn robots_encoding() {
+    let allowed = |content: &str, path: &str| {
+        robots(&string_concat!("user-agent: FooBot\n", content))
+            .can_fetch("FooBot", &string_concat!("https://example.com", path))
+    };
+
+    assert!(!allowed("disallow: /foo/bar/%62%61%7A\n", "/foo/bar/baz"));
+    assert!(!allowed("disallow: /foo/bar/baz\n", "/foo/bar/%62%61%7A"));
+    assert!(!allowed("disallow: /foo/bar/ツ\n", "/foo/bar/%E3%83%84"));
+    assert!(!allowed(
+        "disallow: /foo/bar/%e3%83%84\n",
+        "/foo/bar/%E3%83%84"
+    ));
+    assert!(allowed("disallow: /foo/bar/%2F\n", "/foo/bar//"));
+}
+
+#[test]
+fn robots_groups() {
+    let parser = robots(
+        "allow: /foo/bar/\n\nuser-agent: FooBot\ndisallow: /\nallow: /x/\nuser-agent: BarBot\ndisallow: /\nallow: /y/\n\n\nallow: /w/\nuser-agent: BazBot\n\nuser-agent: FooBot\nallow: /z/\ndisallow: /\n",
+    );
+
+    assert!(parser.can_fetch("FooBot", "https://example.com/x/b"));
+    assert!(parser.can_fetch("FooBot", "https://example.com/z/d"));
+    assert!(!parser.can_fetch("FooBot", "https://example.com/y/c"));
+    assert!(parser.can_fetch("BarBot", "https://example.com/y/c"));
+    assert!(parser.can_fetch("BarBot", "https://example.com/w/a"));
+    assert!(!parser.can_fetch("BarBot", "https://example.com/z/d"));
+    assert!(parser.can_fetch("BazBot", "https://example.com/z/d"));
+    assert!(parser.can_fetch("QuxBot", "https://example.com/foo/bar/"));
+
+    let parser = robots(
+        "User-Agent: *\nDisallow: /\nCrawl-delay: 2\nUser-Agent: Foo Bar\nAllow: /x/\nDisallow: /\nuseragent: BazBot\ndisallow /\n",
+    );
+
+    assert!(parser.can_fetch("Foo", "https://example.com/x/y"));
+    assert!(parser.can_fetch("foo/1.0 (compatible)", "https://example.com/x/y"));
+    assert!(!parser.can_fetch("Bar", "https://example.com/x/y"));
+    assert!(!parser.can_fetch("BazBot", "https://example.com/x/y"));
+    assert_eq!(
+        parser.get_crawl_delay(&Some(Box::new("Bar".into()))),
+        Some(Duration::from_secs(2))
+    );
+    assert_eq!(parser.get_crawl_delay(&Some(Box::new("Foo".into()))), None);
+}
+
+#[test]
+fn robots_sitemaps() {
+    let parser = robots(
+        "Sitemap: https://example.com/sitemap.xml\nUser-agent: FooBot\nSitemap: https://example.com/news.xml\nDisallow: /x/\nsitemap: https://example.com/sitemap.xml\n",
+    );
+
+    assert_eq!(
+        parser.get_sitemaps(),
+        [
+            "https://example.com/sitemap.xml",
+            "https://example.com/news.xml"
+        ]
+    );
+    assert!(!parser.can_fetch("FooBot", "https://example.com/x/y"));
+}
+
+#[test]
+fn robots_status() {
+    let mut parser = robots("User-agent: *\nDisallow: /\n");
+
+    assert!(parser.set_status(StatusCode::OK));
+    assert!(!parser.can_fetch("FooBot", "https://example.com/x/"));
+
+    let mut unavailable = parser.clone();
+    assert!(!unavailable.set_status(StatusCode::FORBIDDEN));
+    assert!(unavailable.can_fetch("FooBot", "https://example.com/x/"));
+    assert!(!unavailable.is_unreachable());
+
+    assert!(!parser.set_status(StatusCode::SERVICE_UNAVAILABLE));
+    assert!(parser.is_unreachable());
+    assert!(!parser.can_fetch("FooBot", "https://example.com/robots.txt"));
+}
@@ -1,3 +1,8 @@
+use crate::features::incremental::Validators;
+use crate::features::link_hook::LinkMetadata;
+use crate::features::normalize::UrlNormalization;
+use crate::features::response_filter::{ResponseFilter, ResponseRejection};
+use crate::features::robots_meta::{is_nofollow, RobotsMeta};
 #[cfg(not(feature = "decentralized"))]
 use crate::packages::scraper::Html;
 use crate::utils::log;
@@ -6,9 +11,10 @@ use crate::CaseInsensitiveString;
 use crate::Client;
 use bytes::Bytes;
 use compact_str::CompactString;
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 use reqwest::StatusCode;
 use smallvec::SmallVec;
+use std::sync::Arc;
 
 #[cfg(all(feature = "time", not(feature = "decentralized")))]
 use std::time::Duration;
@@ -80,6 +86,17 @@ pub struct AIResults {
     pub error: Option<String>,
 }
 
+/// The anchor of a link found on a page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkAnchor {
+    /// The text of the anchor.
+    pub text: String,
+    /// The rel attribute values of the anchor in lowercase like `nofollow`.
+    pub rel: Vec<String>,
+    /// The absolute url of the href before it was normalized if the url normalization changed it.
+    pub original: Option<String>,
+}
+
 /// Represent a page visited. This page contains HTML scraped with [scraper](https://crates.io/crates/scraper).
 #[derive(Debug, Clone)]
 #[cfg(not(feature = "decentralized"))]
@@ -90,6 +107,10 @@ pub struct Page {
     base: Option<Url>,
     /// The raw url for the page. Useful since Url::parse adds a trailing slash.
     url: String,
+    /// Normalize the links found.
+    url_normalization: Option<Arc<UrlNormalization>>,
+    /// The scope selectors of the other start urls of the crawl.
+    seed_scopes: Option<Arc<SeedScopes>>,
     #[cfg(feature = "headers")]
     /// The headers of the page request response.
     pub headers: Option<HeaderMap>,
@@ -101,6 +122,38 @@ pub struct Page {
     pub external_domains_caseless: Box<HashSet<CaseInsensitiveString>>,
     /// The final destination of the page if redirects were performed [Not implemented in the chrome feature].
     pub final_redirect_destination: Option<String>,
+    /// The link hops from the start page of the crawl.
+    pub depth: usize,
+    /// The delay requested by the server with the Retry-After header.
+    pub retry_after: Option<std::time::Duration>,
+    /// The kind of network error the last attempt failed with.
+    pub error_kind: Option<crate::features::retry::RetryErrorKind>,
+    /// The attempts made to fetch the page.
+    pub attempts: u32,
+    /// The time the last request attempt took.
+    pub latency: Option<std::time::Duration>,
+    /// The url before it was normalized if it changed.
+    pub original_url: Option<String>,
+    /// The simhash fingerprint of the page text when near-duplicate detection is enabled.
+    pub fingerprint: Option<u64>,
+    /// The url of an earlier page this page is a near-duplicate of. The links of the page are not followed.
+    pub duplicate_of: Option<String>,
+    /// The ETag and Last-Modified validators of the page response.
+    pub validators: Option<Validators>,
+    /// The page did not change since the last crawl with incremental recrawls. The links of the page are not followed unless `follow_unchanged` is set.
+    pub unchanged: bool,
+    /// The metadata the link hook attached to the link of the page.
+    pub link_metadata: Option<LinkMetadata>,
+    /// The response filter that rejected the response. The page has no html when rejected.
+    pub response_rejected: Option<ResponseRejection>,
+    /// The page asked not to be indexed with a meta robots tag or X-Robots-Tag header. This is only set with `respect_robots_meta`.
+    pub noindex: bool,
+    /// The page asked not to follow its links with a meta robots tag or X-Robots-Tag header. The links of the page are not followed. This is only set with `respect_robots_meta`.
+    pub nofollow: bool,
+    /// Skip the anchors with rel=nofollow.
+    respect_robots_meta: bool,
+    /// The X-Robots-Tag header values of the response.
+    x_robots_tag: Vec<String>,
     #[cfg(feature = "time")]
     /// The duration from start of parsing to end of gathering links.
     duration: Instant,
@@ -124,6 +177,10 @@ pub struct Page {
 pub struct Page {
     /// The bytes of the resource.
     html: Option<Bytes>,
+    /// Normalize the links found.
+    url_normalization: Option<Arc<UrlNormalization>>,
+    /// The scope selectors of the other start urls of the crawl.
+    seed_scopes: Option<Arc<SeedScopes>>,
     #[cfg(feature = "headers")]
     /// The headers of the page request response.
     pub headers: Option<HeaderMap>,
@@ -137,6 +194,36 @@ pub struct Page {
     pub external_domains_caseless: Box<HashSet<CaseInsensitiveString>>,
     /// The final destination of the page if redirects were performed [Unused].
     pub final_redirect_destination: Option<String>,
+    /// The link hops from the start page of the crawl.
+    pub depth: usize,
+    /// The delay requested by the server with the Retry-After header.
+    pub retry_after: Option<std::time::Duration>,
+    /// The kind of network error the last attempt failed with.
+    pub error_kind: Option<crate::features::retry::RetryErrorKind>,
+    /// The attempts made to fetch the page.
+    pub attempts: u32,
+    /// The time the last request attempt took.
+    pub latency: Option<std::time::Duration>,
+    /// The url before it was normalized if it changed.
+    pub original_url: Option<String>,
+    /// The simhash fingerprint of the page text when near-duplicate detection is enabled.
+    pub fingerprint: Option<u64>,
+    /// The url of an earlier page this page is a near-duplicate of. The links of the page are not followed.
+    pub duplicate_of: Option<String>,
+    /// The ETag and Last-Modified validators of the page response.
+    pub validators: Option<Validators>,
+    /// The page did not change since the last crawl with incremental recrawls. The links of the page are not followed unless `follow_unchanged` is set.
+    pub unchanged: bool,
+    /// The metadata the link hook attached to the link of the page.
+    pub link_metadata: Option<LinkMetadata>,
+    /// The response filter that rejected the response. The page has no html when rejected.
+    pub response_rejected: Option<ResponseRejection>,
+    /// The page asked not to be indexed with a meta robots tag or X-Robots-Tag header. This is only set with `respect_robots_meta`.
+    pub noindex: bool,
+    /// The page asked not to follow its links with a meta robots tag or X-Robots-Tag header. The links of the page are not followed. This is only set with `respect_robots_meta`.
+    pub nofollow: bool,
+    /// Skip the anchors with rel=nofollow.
+    respect_robots_meta: bool,
     #[cfg(feature = "chrome")]
     /// The screenshot bytes of the page.
     pub screenshot_bytes: Option<Vec<u8>>,
@@ -214,6 +301,9 @@ pub fn parent_host_match(
     }
 }
 
+/// The scope selectors of the start urls of a crawl.
+pub type SeedScopes = Vec<(CompactString, SmallVec<[CompactString; 2]>)>;
+
 /// html selector for valid web pages for domain.
 pub fn get_page_selectors(
     url: &str,
@@ -265,10 +355,28 @@ pub fn build(url: &str, res: PageResponse) -> Page {
             _ => None,
         },
         url: url.into(),
+        url_normalization: None,
+        seed_scopes: None,
+        original_url: None,
+        fingerprint: None,
+        duplicate_of: None,
+        validators: res.validators,
+        unchanged: false,
+        link_metadata: None,
+        response_rejected: res.response_rejected,
+        noindex: false,
+        nofollow: false,
+        respect_robots_meta: false,
+        x_robots_tag: res.x_robots_tag,
         #[cfg(feature = "time")]
         duration: Instant::now(),
         external_domains_caseless: Default::default(),
         final_redirect_destination: res.final_url,
+        depth: 0,
+        retry_after: res.retry_after,
+        error_kind: res.error_kind,
+        attempts: res.attempts.max(1),
+        latency: res.latency,
         status_code: res.status_code,
         error_status: match res.error_for_status {
             Some(e) => match e {
@@ -300,6 +408,12 @@ pub fn build(_: &str, res: PageResponse) -> Page {
         #[cfg(feature = "headers")]
         headers: res.headers,
         final_redirect_destination: res.final_url,
+        retry_after: res.retry_after,
+        error_kind: res.error_kind,
+        attempts: res.attempts.max(1),
+        latency: res.latency,
+        validators: res.validators,
+        response_rejected: res.response_rejected,
         status_code: res.status_code,
         error_status: match res.error_for_status {
             Some(e) => match e {
@@ -319,6 +433,19 @@ impl Page {
         build(url, page_resource)
     }
 
+    /// Instantiate a new page with a conditional request using the validators of the last crawl. The html is empty if the page was not modified or the response filter rejected the response.
+    pub async fn new_page_conditional(
+        url: &str,
+        client: &Client,
+        validators: Option<&Validators>,
+        response_filter: Option<&ResponseFilter>,
+    ) -> Self {
+        let page_resource =
+            crate::utils::fetch_page_html_raw_conditional(url, client, validators, response_filter)
+                .await;
+        build(url, page_resource)
+    }
+
     /// Instantiate a new page and gather the html.
     #[cfg(all(not(feature = "decentralized"), not(feature = "chrome")))]
     pub async fn new(url: &str, client: &Client) -> Self {
@@ -326,6 +453,20 @@ impl Page {
         build(url, page_resource)
     }
 
+    /// Instantiate a new page and gather the html with a conditional request using the validators of the last crawl. The html is empty if the page was not modified or the response filter rejected the response.
+    #[cfg(all(not(feature = "decentralized"), not(feature = "chrome")))]
+    pub async fn new_conditional(
+        url: &str,
+        client: &Client,
+        validators: Option<&Validators>,
+        response_filter: Option<&ResponseFilter>,
+    ) -> Self {
+        let page_resource =
+            crate::utils::fetch_page_html_conditional(url, client, validators, response_filter)
+                .await;
+        build(url, page_resource)
+    }
+
     #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
     /// Instantiate a new page and gather the html.
     pub async fn new(
@@ -337,7 +478,32 @@ impl Page {
         page_set: bool,
         openai_config: &Option<crate::configuration::GPTConfigs>,
     ) -> Self {
-        let page_resource = crate::utils::fetch_page_html(
+        Self::new_filtered(
+            url,
+            client,
+            page,
+            wait_for,
+            screenshot,
+            page_set,
+            openai_config,
+            None,
+        )
+        .await
+    }
+
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    /// Instantiate a new page and gather the html. The html is empty if the response filter rejected the response.
+    pub async fn new_filtered(
+        url: &str,
+        client: &Client,
+        page: &chromiumoxide::Page,
+        wait_for: &Option<crate::configuration::WaitFor>,
+        screenshot: &Option<crate::configuration::ScreenShotConfig>,
+        page_set: bool,
+        openai_config: &Option<crate::configuration::GPTConfigs>,
+        response_filter: Option<&ResponseFilter>,
+    ) -> Self {
+        let page_resource = crate::utils::fetch_page_html_filtered(
             &url,
             &client,
             &page,
@@ -345,6 +511,7 @@ impl Page {
             screenshot,
             page_set,
             openai_config,
+            response_filter,
         )
         .await;
         let mut p = build(url, page_resource);
@@ -572,6 +739,40 @@ impl Page {
         self.external_domains_caseless = external_domains_caseless;
     }
 
+    /// Set the normalization for the links found.
+    pub fn set_url_normalization(&mut self, url_normalization: Option<Arc<UrlNormalization>>) {
+        self.url_normalization = url_normalization;
+    }
+
+    /// Set the scope selectors of the other start urls so links in their scope are kept.
+    pub fn set_seed_scopes(&mut self, seed_scopes: Option<Arc<SeedScopes>>) {
+        self.seed_scopes = seed_scopes;
+    }
+
+    /// Read the meta robots tags and X-Robots-Tag headers of the page. The links of nofollow pages and rel=nofollow anchors are not followed and noindex pages are flagged.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn set_robots_meta(&mut self, robots_meta: Option<Arc<RobotsMeta>>) {
+        match robots_meta {
+            Some(robots_meta) => {
+                let directives = robots_meta.directives(&self.get_html(), &self.x_robots_tag);
+
+                self.noindex = directives.noindex;
+                self.nofollow = directives.nofollow;
+                self.respect_robots_meta = true;
+            }
+            _ => (),
+        }
+    }
+
+    /// Read the meta robots tags and X-Robots-Tag headers of the page. The directives are not sent from the worker.
+    #[cfg(feature = "decentralized")]
+    pub fn set_robots_meta(&mut self, _: Option<Arc<RobotsMeta>>) {}
+
+    /// The anchor is rel=nofollow and skipped with `respect_robots_meta`.
+    fn is_nofollow_anchor(&self, rel: Option<&str>) -> bool {
+        self.respect_robots_meta && is_nofollow(rel)
+    }
+
     /// Set the html directly of the page
     pub fn set_html_bytes(&mut self, html: Option<Bytes>) {
         self.html = html;
@@ -744,6 +945,22 @@ impl Page {
                 let host_name = abs.host_str();
                 let mut can_process = parent_host_match(host_name, base_domain, parent_host);
                 let mut external_domain = false;
+                let mut parent_host_scheme = parent_host_scheme;
+
+                if !can_process && host_name.is_some() {
+                    match self.seed_scopes.as_ref().and_then(|scopes| {
+                        scopes
+                            .iter()
+                            .find(|s| parent_host_match(host_name, &s.0, &s.1[0]))
+                    }) {
+                        Some(scope) => {
+                            can_process = true;
+                            external_domain = true;
+                            parent_host_scheme = &scope.1[1];
+                        }
+                        _ => (),
+                    }
+                }
 
                 if !can_process && host_name.is_some() && !self.external_domains_caseless.is_empty()
                 {
@@ -761,6 +978,11 @@ impl Page {
                         let _ = abs.set_scheme(parent_host_scheme.as_str());
                     }
 
+                    match &self.url_normalization {
+                        Some(normalization) => normalization.normalize(&mut abs),
+                        _ => (),
+                    }
+
                     let hchars = abs.path();
 
                     if let Some(position) = hchars.rfind('.') {
@@ -879,7 +1101,7 @@ impl Page {
                 if let Some(element) = node.as_element() {
                     let element_name = element.name();
 
-                    if element_name == "a" {
+                    if element_name == "a" && !self.is_nofollow_anchor(element.attr("rel")) {
                         match element.attr("href") {
                             Some(href) => {
                                 self.push_link(
@@ -1078,28 +1300,33 @@ impl Page {
                                                                     );
                                                             }
 
-                                                            let page_resource =
-                                                            crate::utils::fetch_page_html_chrome_base(
-                                                                &uu,
-                                                                &new_page,
+                                                            let wait_for = Some(crate::configuration::WaitFor::new(
+                                                                Some(
+                                                                    core::time::Duration::from_secs(
+                                                                        120,
+                                                                    ), // default a duration for smart handling. (maybe expose later on.)
+                                                                ),
+                                                                None,
                                                                 true,
-                                                                false,
-                                                                &Some(crate::configuration::WaitFor::new(
-                                                                    Some(
-                                                                        core::time::Duration::from_secs(
-                                                                            120,
-                                                                        ), // default a duration for smart handling. (maybe expose later on.)
-                                                                    ),
-                                                                    None,
-                                                                    true,
+                                                                true,
+                                                                None,
+                                                            ));
+
+                                                            let page_resource =
+                                                            crate::features::retry::retry_fetch(&configuration.retry_policy, || {
+                                                                crate::utils::fetch_page_html_chrome_base(
+                                                                    &uu,
+                                                                    &new_page,
                                                                     true,
+                                                                    false,
+                                                                    &wait_for,
+                                                                    &configuration.screenshot,
+                                                                    false,
+                                                                    &configuration.openai_config,
+                                                                    Some(&target_url),
                                                                     None,
-                                                                )),
-                                                                &configuration.screenshot,
-                                                                false,
-                                                                &configuration.openai_config,
-                                                                Some(&target_url)
-                                                            )
+                                                                )
+                                                            })
                                                             .await;
 
                                                             match configuration.execution_scripts {
@@ -1149,7 +1376,7 @@ impl Page {
                         }
                     }
 
-                    if element_name == "a" {
+                    if element_name == "a" && !self.is_nofollow_anchor(element.attr("rel")) {
                         // add fullresources?
                         match element.attr("href") {
                             Some(href) => match self.abs_path(href) {
@@ -1240,6 +1467,10 @@ impl Page {
                 if let Some(element) = node.as_element() {
                     let element_name = element.name();
 
+                    if element_name == "a" && self.is_nofollow_anchor(element.attr("rel")) {
+                        continue;
+                    }
+
                     let ele_attribute = if element_name == "a" || element_name == "link" {
                         "href"
                     } else if element_name == "script" {
@@ -1326,7 +1557,7 @@ impl Page {
         &self,
         selectors: &(CompactString, SmallVec<[CompactString; 2]>),
     ) -> HashSet<CaseInsensitiveString> {
-        match self.html.is_some() {
+        match self.html.is_some() && !self.nofollow {
             false => Default::default(),
             true => {
                 self.links_stream::<CaseInsensitiveString>(&(&selectors.0, &selectors.1))
@@ -1341,7 +1572,7 @@ impl Page {
         &self,
         selectors: &(CompactString, SmallVec<[CompactString; 2]>),
     ) -> HashSet<CaseInsensitiveString> {
-        match self.html.is_some() {
+        match self.html.is_some() && !self.nofollow {
             false => Default::default(),
             true => {
                 self.links_stream_full_resource::<CaseInsensitiveString>(&(
@@ -1362,7 +1593,7 @@ impl Page {
         page: &std::sync::Arc<chromiumoxide::Browser>,
         configuration: &crate::configuration::Configuration,
     ) -> HashSet<CaseInsensitiveString> {
-        match self.html.is_some() {
+        match self.html.is_some() && !self.nofollow {
             false => Default::default(),
             true => {
                 self.links_stream_smart::<CaseInsensitiveString>(
@@ -1385,6 +1616,105 @@ impl Page {
         self.links.to_owned()
     }
 
+    /// Find the anchor text of the href links. The keys match the links returned from `links`.
+    pub fn links_anchor_text(
+        &self,
+        selectors: &(CompactString, SmallVec<[CompactString; 2]>),
+    ) -> HashMap<CaseInsensitiveString, String> {
+        self.links_anchors(selectors)
+            .into_iter()
+            .map(|(link, anchor)| (link, anchor.text))
+            .collect()
+    }
+
+    /// Find the anchor text and rel attributes of the href links. The keys match the links returned from `links`.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn links_anchors(
+        &self,
+        selectors: &(CompactString, SmallVec<[CompactString; 2]>),
+    ) -> HashMap<CaseInsensitiveString, LinkAnchor> {
+        use crate::packages::scraper::Selector;
+        lazy_static! {
+            static ref ANCHOR_SELECTOR: Selector = Selector::parse("a[href]").unwrap();
+        }
+        let mut anchors = HashMap::new();
+        let html = self.get_html();
+
+        if self.html.is_none() || html.starts_with("<?xml") {
+            return anchors;
+        }
+
+        let html = Html::parse_fragment(&html);
+        let parent_host = &selectors.1[0];
+        let parent_host_scheme = &selectors.1[1];
+
+        for element in html.select(&ANCHOR_SELECTOR) {
+            match element.attr("href") {
+                Some(href) => {
+                    let mut link: HashSet<CaseInsensitiveString> = HashSet::with_capacity(1);
+
+                    self.push_link(
+                        href,
+                        &mut link,
+                        &selectors.0,
+                        parent_host,
+                        parent_host_scheme,
+                    );
+
+                    match link.into_iter().next() {
+                        Some(link) => {
+                            let original = match self.url_normalization {
+                                Some(_) => self
+                                    .abs_path(href)
+                                    .map(|abs| abs.as_str().to_string())
+                                    .filter(|abs| abs.as_str() != link.inner().as_str()),
+                                _ => None,
+                            };
+                            let anchor: &mut LinkAnchor = anchors.entry(link).or_default();
+
+                            if anchor.original.is_none() {
+                                anchor.original = original;
+                            }
+
+                            if anchor.text.is_empty() {
+                                anchor.text = element
+                                    .text()
+                                    .flat_map(|t| t.split_whitespace())
+                                    .collect::<Vec<_>>()
+                                    .join(" ");
+                            }
+
+                            match element.attr("rel") {
+                                Some(rel) => {
+                                    for rel in rel.split_whitespace() {
+                                        let rel = rel.to_ascii_lowercase();
+                                        if !anchor.rel.contains(&rel) {
+                                            anchor.rel.push(rel);
+                                        }
+                                    }
+                                }
+                                _ => (),
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        anchors
+    }
+
+    /// Find the anchor text and rel attributes of the href links. The anchors are not sent from the worker.
+    #[cfg(feature = "decentralized")]
+    pub fn links_anchors(
+        &self,
+        _: &(CompactString, smallvec::SmallVec<[CompactString; 2]>),
+    ) -> HashMap<CaseInsensitiveString, LinkAnchor> {
+        Default::default()
+    }
+
     /// Convert a URL to its absolute path without any fragments or params.
     #[inline]
     #[cfg(not(feature = "decentralized"))]
@@ -1547,3 +1877,38 @@ async fn test_duration() {
         duration_elasped,
     );
 }
+
+#[cfg(not(feature = "decentralized"))]
+#[tokio::test]
+async fn test_robots_meta() {
+    let link_result = "https://choosealicense.com/";
+    let selector = get_page_selectors(link_result, false, false).unwrap();
+    let response = |html: &str, x_robots_tag: &[&str]| PageResponse {
+        content: Some(html.to_string().into()),
+        x_robots_tag: x_robots_tag.iter().map(|v| v.to_string()).collect(),
+        ..Default::default()
+    };
+    let html = r#"<html><body><a href="/about/">About</a><a href="/licenses/" rel="NoFollow">Licenses</a></body></html>"#;
+
+    let mut page = build(link_result, response(html, &[]));
+    page.set_robots_meta(Some(Arc::new(RobotsMeta::new(Some("FooBot/2.1")))));
+    let links = page.links(&selector).await;
+
+    assert!(!page.noindex);
+    assert!(links.contains::<CaseInsensitiveString>(&"https://choosealicense.com/about/".into()));
+    assert!(
+        !links.contains::<CaseInsensitiveString>(&"https://choosealicense.com/licenses/".into())
+    );
+
+    let mut page = build(link_result, response(html, &["foobot: noindex, nofollow"]));
+    page.set_robots_meta(Some(Arc::new(RobotsMeta::new(Some("FooBot/2.1")))));
+
+    assert!(page.noindex);
+    assert!(page.links(&selector).await.is_empty());
+
+    let mut page = build(link_result, response(html, &["foobot: noindex, nofollow"]));
+    page.set_robots_meta(None);
+
+    assert!(!page.noindex);
+    assert_eq!(page.links(&selector).await.len(), 2);
+}
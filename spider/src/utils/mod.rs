@@ -1,6 +1,10 @@
 /// Utils to modify the HTTP header.
 pub mod header_utils;
 
+use crate::features::incremental::Validators;
+use crate::features::response_filter::{ResponseFilter, ResponseRejection};
+use crate::features::retry::RetryErrorKind;
+use crate::features::robots_meta::x_robots_tag;
 use crate::tokio_stream::StreamExt;
 use crate::Client;
 #[cfg(feature = "cache_chrome_hybrid")]
@@ -108,6 +112,20 @@ pub struct PageResponse {
     pub final_url: Option<String>,
     /// The message of the response error if any.
     pub error_for_status: Option<Result<Response, Error>>,
+    /// The delay requested with the Retry-After header of the response.
+    pub retry_after: Option<std::time::Duration>,
+    /// The kind of network error the request failed with.
+    pub error_kind: Option<crate::features::retry::RetryErrorKind>,
+    /// The attempts made for the request.
+    pub attempts: u32,
+    /// The time the last request attempt took.
+    pub latency: Option<std::time::Duration>,
+    /// The ETag and Last-Modified validators of the response.
+    pub validators: Option<Validators>,
+    /// The X-Robots-Tag header values of the response.
+    pub x_robots_tag: Vec<String>,
+    /// The response filter that rejected the response. The body is not downloaded.
+    pub response_rejected: Option<ResponseRejection>,
     #[cfg(feature = "chrome")]
     /// The screenshot bytes of the page. The ScreenShotConfig bytes boolean needs to be set to true.
     pub screenshot_bytes: Option<Vec<u8>>,
@@ -333,6 +351,10 @@ pub struct ChromeHTTPReqRes {
     request_headers: std::collections::HashMap<String, String>,
     /// The HTTP protocol of the request.
     protocol: String,
+    /// The HTTP response headers checked with the response filter.
+    headers: HeaderMap,
+    /// The X-Robots-Tag header values of the response.
+    x_robots_tag: Vec<String>,
 }
 
 #[cfg(feature = "chrome")]
@@ -347,6 +369,7 @@ pub async fn perform_chrome_http_request(
     let mut response_headers = std::collections::HashMap::default();
     let mut request_headers = std::collections::HashMap::default();
     let mut protocol = String::from("http/1.1");
+    let mut headers = HeaderMap::new();
 
     match page
         .http_future(chromiumoxide::cdp::browser_protocol::page::NavigateParams {
@@ -380,6 +403,23 @@ pub async fn perform_chrome_http_request(
                     match response.headers.inner().as_object() {
                         Some(res_headers) => {
                             for (k, v) in res_headers {
+                                match (
+                                    reqwest::header::HeaderName::from_bytes(k.as_bytes()),
+                                    v.as_str(),
+                                ) {
+                                    // the values of a repeated header are joined with a new line.
+                                    (Ok(name), Some(v)) => {
+                                        for value in v.split('\n') {
+                                            match reqwest::header::HeaderValue::from_str(value) {
+                                                Ok(value) => {
+                                                    headers.append(&name, value);
+                                                }
+                                                _ => (),
+                                            }
+                                        }
+                                    }
+                                    _ => (),
+                                }
                                 response_headers.insert(k.to_string(), v.to_string());
                             }
                         }
@@ -421,6 +461,8 @@ pub async fn perform_chrome_http_request(
         response_headers,
         request_headers,
         protocol,
+        x_robots_tag: x_robots_tag(&headers),
+        headers,
     })
 }
 
@@ -759,7 +801,7 @@ pub async fn put_hybrid_cache(
 }
 
 #[cfg(feature = "chrome")]
-/// Perform a network request to a resource extracting all content as text streaming via chrome.
+/// Perform a network request to a resource extracting all content as text streaming via chrome. The page is not rendered if the response filter rejects the response.
 pub async fn fetch_page_html_chrome_base(
     source: &str,
     page: &chromiumoxide::Page,
@@ -770,6 +812,7 @@ pub async fn fetch_page_html_chrome_base(
     page_set: bool,
     openai_config: &Option<crate::configuration::GPTConfigs>,
     url_target: Option<&str>,
+    response_filter: Option<&ResponseFilter>,
 ) -> Result<PageResponse, chromiumoxide::error::CdpError> {
     let mut chrome_http_req_res = ChromeHTTPReqRes::default();
 
@@ -789,6 +832,32 @@ pub async fn fetch_page_html_chrome_base(
         }
     };
 
+    // the browser has the body by now, but the page is not waited on or read.
+    let response_rejected = match response_filter {
+        Some(filter) if !content && !page_set => filter.check(
+            chrome_http_req_res.status_code,
+            &chrome_http_req_res.headers,
+        ),
+        _ => None,
+    };
+
+    if response_rejected.is_some() {
+        log("- response rejected", source);
+
+        if cfg!(not(feature = "chrome_store_page")) {
+            page.execute(chromiumoxide::cdp::browser_protocol::page::CloseParams::default())
+                .await?;
+        }
+
+        return Ok(PageResponse {
+            #[cfg(feature = "headers")]
+            headers: Some(chrome_http_req_res.headers),
+            status_code: chrome_http_req_res.status_code,
+            response_rejected,
+            ..Default::default()
+        });
+    }
+
     // we do not need to wait for navigation if content is assigned. The method set_content already handles this.
     let final_url = if wait_for_navigation && !content {
         match page.wait_for_navigation_response().await {
@@ -807,7 +876,14 @@ pub async fn fetch_page_html_chrome_base(
         let _ = cf_handle(&mut res, &page).await;
     };
 
-    let ok = res.len() > 0;
+    let response_rejected = match response_filter {
+        Some(filter) if filter.is_over_length(res.len() as u64) => {
+            Some(ResponseRejection::ContentLength)
+        }
+        _ => None,
+    };
+
+    let ok = !res.is_empty() && response_rejected.is_none();
 
     if chrome_http_req_res.waf_check && res.starts_with(b"<html><head>\n    <style global=") && res.ends_with(b";</script><iframe height=\"1\" width=\"1\" style=\"position: absolute; top: 0px; left: 0px; border: none; visibility: hidden;\"></iframe>\n\n</body></html>"){
         chrome_http_req_res.status_code = StatusCode::FORBIDDEN;
@@ -817,6 +893,8 @@ pub async fn fetch_page_html_chrome_base(
         content: if ok { Some(res) } else { None },
         status_code: chrome_http_req_res.status_code,
         final_url,
+        x_robots_tag: std::mem::take(&mut chrome_http_req_res.x_robots_tag),
+        response_rejected,
         ..Default::default()
     };
 
@@ -977,6 +1055,31 @@ pub async fn fetch_page_html(
     screenshot: &Option<crate::configuration::ScreenShotConfig>,
     page_set: bool,
     openai_config: &Option<crate::configuration::GPTConfigs>,
+) -> PageResponse {
+    fetch_page_html_filtered(
+        target_url,
+        client,
+        page,
+        wait_for,
+        screenshot,
+        page_set,
+        openai_config,
+        None,
+    )
+    .await
+}
+
+#[cfg(all(not(feature = "fs"), feature = "chrome"))]
+/// Perform a network request to a resource extracting all content as text streaming via chrome. The page has no content if the response filter rejects the response.
+pub async fn fetch_page_html_filtered(
+    target_url: &str,
+    client: &Client,
+    page: &chromiumoxide::Page,
+    wait_for: &Option<crate::configuration::WaitFor>,
+    screenshot: &Option<crate::configuration::ScreenShotConfig>,
+    page_set: bool,
+    openai_config: &Option<crate::configuration::GPTConfigs>,
+    response_filter: Option<&ResponseFilter>,
 ) -> PageResponse {
     match fetch_page_html_chrome_base(
         &target_url,
@@ -988,13 +1091,14 @@ pub async fn fetch_page_html(
         page_set,
         openai_config,
         None,
+        response_filter,
     )
     .await
     {
         Ok(page) => page,
         Err(err) => {
             log::error!("{:?}", err);
-            fetch_page_html_raw(&target_url, &client).await
+            fetch_page_html_raw_conditional(&target_url, &client, None, response_filter).await
         }
     }
 }
@@ -1021,12 +1125,102 @@ pub fn get_last_redirect(
     }
 }
 
+/// Get the delay requested with the Retry-After header in seconds or as a HTTP date.
+pub fn get_retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(std::time::Duration::from_secs(seconds)),
+        _ => match httpdate::parse_http_date(value) {
+            Ok(date) => Some(
+                date.duration_since(std::time::SystemTime::now())
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        },
+    }
+}
+
 /// Perform a network request to a resource extracting all content streaming.
 pub async fn fetch_page_html_raw(target_url: &str, client: &Client) -> PageResponse {
+    fetch_page_html_raw_conditional(target_url, client, None, None).await
+}
+
+/// Check the response with the response filter before the body is downloaded.
+fn check_response(
+    res: &Result<Response, Error>,
+    response_filter: Option<&ResponseFilter>,
+) -> Option<ResponseRejection> {
+    match (res, response_filter) {
+        (Ok(res), Some(filter)) => filter.check(res.status(), res.headers()),
+        _ => None,
+    }
+}
+
+/// The response rejected by the response filter without the body.
+fn rejected_response(res: Response, rejection: Option<ResponseRejection>) -> PageResponse {
+    log("- response rejected", res.url().as_str());
+    PageResponse {
+        #[cfg(feature = "headers")]
+        headers: Some(res.headers().clone()),
+        status_code: res.status(),
+        response_rejected: rejection,
+        ..Default::default()
+    }
+}
+
+#[cfg(not(feature = "cache"))]
+/// The request builder of the client.
+type RequestBuilder = reqwest::RequestBuilder;
+#[cfg(feature = "cache")]
+/// The request builder of the client.
+type RequestBuilder = reqwest_middleware::RequestBuilder;
+
+/// Add the `If-None-Match` and `If-Modified-Since` headers of the validators to the request.
+fn conditional_request(
+    mut request: RequestBuilder,
+    validators: Option<&Validators>,
+) -> RequestBuilder {
+    match validators {
+        Some(validators) => {
+            match validators.etag {
+                Some(ref etag) => request = request.header(reqwest::header::IF_NONE_MATCH, etag),
+                _ => (),
+            }
+            match validators.last_modified {
+                Some(ref last_modified) => {
+                    request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified)
+                }
+                _ => (),
+            }
+        }
+        _ => (),
+    }
+
+    request
+}
+
+/// Perform a conditional network request to a resource extracting all content streaming. A `304 Not Modified` response has no content. The body is not downloaded if the response filter rejects the response.
+pub async fn fetch_page_html_raw_conditional(
+    target_url: &str,
+    client: &Client,
+    validators: Option<&Validators>,
+    response_filter: Option<&ResponseFilter>,
+) -> PageResponse {
     use crate::bytes::BufMut;
     use bytes::BytesMut;
 
-    match client.get(target_url).send().await {
+    let res = conditional_request(client.get(target_url), validators)
+        .send()
+        .await;
+    let rejection = check_response(&res, response_filter);
+
+    match res {
+        Ok(res) if rejection.is_some() => rejected_response(res, rejection),
         Ok(res) if res.status().is_success() => {
             let u = res.url().as_str();
 
@@ -1036,10 +1230,13 @@ pub async fn fetch_page_html_raw(target_url: &str, client: &Client) -> PageRespo
                 None
             };
             let status_code = res.status();
+            let validators = Validators::from_headers(res.headers());
+            let x_robots_tag = x_robots_tag(res.headers());
             #[cfg(feature = "headers")]
             let headers = res.headers().clone();
             let mut stream = res.bytes_stream();
             let mut data: BytesMut = BytesMut::new();
+            let mut response_rejected = None;
 
             while let Some(item) = stream.next().await {
                 match item {
@@ -1050,6 +1247,16 @@ pub async fn fetch_page_html_raw(target_url: &str, client: &Client) -> PageRespo
                             break;
                         }
 
+                        match response_filter {
+                            Some(filter)
+                                if filter.is_over_length((data.len() + text.len()) as u64) =>
+                            {
+                                response_rejected = Some(ResponseRejection::ContentLength);
+                                break;
+                            }
+                            _ => (),
+                        }
+
                         data.put(text)
                     }
                     _ => (),
@@ -1059,9 +1266,16 @@ pub async fn fetch_page_html_raw(target_url: &str, client: &Client) -> PageRespo
             PageResponse {
                 #[cfg(feature = "headers")]
                 headers: Some(headers),
-                content: Some(data.into()),
+                content: if response_rejected.is_some() {
+                    None
+                } else {
+                    Some(data.into())
+                },
                 final_url: rd,
                 status_code,
+                validators,
+                x_robots_tag,
+                response_rejected,
                 ..Default::default()
             }
         }
@@ -1069,11 +1283,17 @@ pub async fn fetch_page_html_raw(target_url: &str, client: &Client) -> PageRespo
             #[cfg(feature = "headers")]
             headers: Some(res.headers().clone()),
             status_code: res.status(),
+            retry_after: get_retry_after(res.headers()),
+            validators: Validators::from_headers(res.headers()),
             ..Default::default()
         },
-        Err(_) => {
+        Err(e) => {
             log("- error parsing html text {}", target_url);
-            Default::default()
+            PageResponse {
+                error_kind: Some(RetryErrorKind::from_error(&e)),
+                error_for_status: Some(Err(e)),
+                ..Default::default()
+            }
         }
     }
 }
@@ -1084,6 +1304,17 @@ pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse
     fetch_page_html_raw(target_url, client).await
 }
 
+#[cfg(all(not(feature = "fs"), not(feature = "chrome")))]
+/// Perform a conditional network request to a resource extracting all content as text streaming. A `304 Not Modified` response has no content.
+pub async fn fetch_page_html_conditional(
+    target_url: &str,
+    client: &Client,
+    validators: Option<&Validators>,
+    response_filter: Option<&ResponseFilter>,
+) -> PageResponse {
+    fetch_page_html_raw_conditional(target_url, client, validators, response_filter).await
+}
+
 /// Perform a network request to a resource extracting all content as text.
 #[cfg(feature = "decentralized")]
 pub async fn fetch_page(target_url: &str, client: &Client) -> Option<bytes::Bytes> {
@@ -1140,6 +1371,17 @@ pub async fn fetch_page_and_headers(target_url: &str, client: &Client) -> FetchP
 /// Perform a network request to a resource extracting all content as text streaming.
 #[cfg(feature = "fs")]
 pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse {
+    fetch_page_html_conditional(target_url, client, None, None).await
+}
+
+/// Perform a conditional network request to a resource extracting all content as text streaming. A `304 Not Modified` response has no content. The body is not downloaded if the response filter rejects the response.
+#[cfg(feature = "fs")]
+pub async fn fetch_page_html_conditional(
+    target_url: &str,
+    client: &Client,
+    validators: Option<&Validators>,
+    response_filter: Option<&ResponseFilter>,
+) -> PageResponse {
     use crate::bytes::BufMut;
     use crate::tokio::io::AsyncReadExt;
     use crate::tokio::io::AsyncWriteExt;
@@ -1173,7 +1415,13 @@ pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse
         };
     };
 
-    match client.get(target_url).send().await {
+    let res = conditional_request(client.get(target_url), validators)
+        .send()
+        .await;
+    let rejection = check_response(&res, response_filter);
+
+    match res {
+        Ok(res) if rejection.is_some() => rejected_response(res, rejection),
         Ok(res) if res.status().is_success() => {
             let u = res.url().as_str();
 
@@ -1184,17 +1432,30 @@ pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse
             };
 
             let status_code = res.status();
+            let validators = Validators::from_headers(res.headers());
+            let x_robots_tag = x_robots_tag(res.headers());
             #[cfg(feature = "headers")]
             let headers = res.headers().clone();
             let mut stream = res.bytes_stream();
             let mut data: BytesMut = BytesMut::new();
             let mut file: Option<tokio::fs::File> = None;
             let mut file_path = String::new();
+            let mut total_bytes: u64 = 0;
+            let mut response_rejected = None;
 
             while let Some(item) = stream.next().await {
                 match item {
                     Ok(text) => {
                         let wrote_disk = file.is_some();
+                        total_bytes += text.len() as u64;
+
+                        match response_filter {
+                            Some(filter) if filter.is_over_length(total_bytes) => {
+                                response_rejected = Some(ResponseRejection::ContentLength);
+                                break;
+                            }
+                            _ => (),
+                        }
 
                         // perform operations entire in memory to build resource
                         if !wrote_disk && data.capacity() < 8192 {
@@ -1235,7 +1496,12 @@ pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse
             PageResponse {
                 #[cfg(feature = "headers")]
                 headers: Some(headers),
-                content: Some(if file.is_some() {
+                content: if response_rejected.is_some() {
+                    if file.is_some() {
+                        let _ = tokio::fs::remove_file(file_path).await;
+                    }
+                    None
+                } else if file.is_some() {
                     let mut buffer = vec![];
 
                     match tokio::fs::File::open(&file_path).await {
@@ -1249,12 +1515,15 @@ pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse
                         _ => (),
                     };
 
-                    buffer.into()
+                    Some(buffer.into())
                 } else {
-                    data.into()
-                }),
+                    Some(data.into())
+                },
                 status_code,
                 final_url: rd,
+                validators,
+                x_robots_tag,
+                response_rejected,
                 ..Default::default()
             }
         }
@@ -1262,11 +1531,17 @@ pub async fn fetch_page_html(target_url: &str, client: &Client) -> PageResponse
             #[cfg(feature = "headers")]
             headers: Some(res.headers().clone()),
             status_code: res.status(),
+            retry_after: get_retry_after(res.headers()),
+            validators: Validators::from_headers(res.headers()),
             ..Default::default()
         },
-        Err(_) => {
+        Err(e) => {
             log("- error parsing html text {}", &target_url);
-            Default::default()
+            PageResponse {
+                error_kind: Some(RetryErrorKind::from_error(&e)),
+                error_for_status: Some(Err(e)),
+                ..Default::default()
+            }
         }
     }
 }
@@ -1281,6 +1556,7 @@ pub async fn fetch_page_html_chrome(
     screenshot: &Option<crate::configuration::ScreenShotConfig>,
     page_set: bool,
     openai_config: &Option<crate::configuration::GPTConfigs>,
+    response_filter: Option<&ResponseFilter>,
 ) -> PageResponse {
     match &page {
         page => {
@@ -1294,6 +1570,7 @@ pub async fn fetch_page_html_chrome(
                 page_set,
                 openai_config,
                 None,
+                response_filter,
             )
             .await
             {
@@ -1307,10 +1584,15 @@ pub async fn fetch_page_html_chrome(
                     use crate::bytes::BufMut;
                     use bytes::BytesMut;
 
-                    match client.get(target_url).send().await {
+                    let res = client.get(target_url).send().await;
+                    let rejection = check_response(&res, response_filter);
+
+                    match res {
+                        Ok(res) if rejection.is_some() => rejected_response(res, rejection),
                         Ok(res) if res.status().is_success() => {
                             #[cfg(feature = "headers")]
                             let headers = res.headers().clone();
+                            let x_robots_tag = x_robots_tag(res.headers());
                             let status_code = res.status();
                             let mut stream = res.bytes_stream();
                             let mut data: BytesMut = BytesMut::new();
@@ -1334,6 +1616,7 @@ pub async fn fetch_page_html_chrome(
                                 headers: Some(headers),
                                 content: Some(data.into()),
                                 status_code,
+                                x_robots_tag,
                                 ..Default::default()
                             }
                         }
@@ -1341,11 +1624,16 @@ pub async fn fetch_page_html_chrome(
                             #[cfg(feature = "headers")]
                             headers: Some(res.headers().clone()),
                             status_code: res.status(),
+                            retry_after: get_retry_after(res.headers()),
                             ..Default::default()
                         },
-                        Err(_) => {
+                        Err(e) => {
                             log("- error parsing html text {}", &target_url);
-                            Default::default()
+                            PageResponse {
+                                error_kind: Some(RetryErrorKind::from_error(&e)),
+                                error_for_status: Some(Err(e)),
+                                ..Default::default()
+                            }
                         }
                     }
                 }
@@ -30,13 +30,28 @@ async fn main() {
         env_logger::init_from_env(env);
     }
 
-    let url = if cli.url.starts_with("http") {
-        cli.url
-    } else {
-        string_concat!("https://", cli.url)
+    let mut seeds = cli.url.clone();
+
+    match cli.seed_file {
+        Some(ref seed_file) => match std::fs::read_to_string(seed_file) {
+            Ok(file) => seeds.extend(
+                file.lines()
+                    .map(|l| l.trim())
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(|l| l.to_string()),
+            ),
+            Err(e) => eprintln!("Unable to read seed file {seed_file}: {e}"),
+        },
+        _ => (),
+    }
+
+    let url = match seeds.first() {
+        Some(url) if url.starts_with("http") => url.to_string(),
+        Some(url) => string_concat!("https://", url),
+        _ => Default::default(),
     };
 
-    let mut website = Website::new(&url);
+    let mut website = Website::from_seeds(&seeds);
 
     website
         .with_respect_robots_txt(cli.respect_robots_txt)
@@ -8,9 +8,12 @@ pub struct Cli {
     /// Build main sub commands
     #[clap(subcommand)]
     pub command: Option<Commands>,
-    /// The website URL to crawl.
-    #[clap(short, long)]
-    pub url: String,
+    /// The website URL to crawl. Pass more than once to crawl many start urls together.
+    #[clap(short, long, required_unless_present = "seed_file")]
+    pub url: Vec<String>,
+    /// File with a start url to crawl on each line.
+    #[clap(long)]
+    pub seed_file: Option<String>,
     /// Respect robots.txt file
     #[clap(short, long)]
     pub respect_robots_txt: bool,